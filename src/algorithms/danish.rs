@@ -1,27 +1,36 @@
 use crate::*;
+use super::PairingSystem;
 
 impl Round {
 
-    pub fn generate_danish(tournament: &Tournament) -> Self {
+    pub fn generate_danish(tournament: &Tournament) -> Result<Self, Error> {
 
         let scores = tournament.get_player_scores();
 
         let mut players_by_score = tournament.get_active_player_ids();
-        players_by_score.sort_by_scores_ascending(&scores);
+        players_by_score.sort_by_scores_ascending(&scores, &tournament.get_pairing_numbers());
         players_by_score.0.reverse();
         
-        let bye_player = players_by_score.odd().then(|| {
-            let player = players_by_score.get_first_player_without_bye(tournament);
+        let mut bye_player = None;
+
+        if players_by_score.odd() {
+
+            let Some(player) = players_by_score.get_first_player_without_bye(tournament)
+            else {
+                return Err(Error::PairingImpossible(PairingSystem::Danish));
+            };
+
             players_by_score.remove(player);
-            player
-        });
+            bye_player = Some(player);
+
+        }
 
-        Self::from_pairings(
+        Ok(Self::from_pairings(
             make_pairings(
                 &players_by_score.pair_off_in_order(),
                 tournament.colour_difference_pairing()
             ),
             bye_player
-        )
+        ))
     }
 }
\ No newline at end of file
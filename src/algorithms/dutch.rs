@@ -1,14 +1,15 @@
 use crate::*;
+use super::PairingSystem;
 use itertools::Itertools;
 
 impl Round {
 
-    pub fn generate_dutch(tournament: &Tournament) -> Self {
+    pub fn generate_dutch(tournament: &Tournament) -> Result<Self, Error> {
 
         let scores = tournament.get_player_scores();
 
         let mut players_by_score = tournament.get_active_player_ids();
-        players_by_score.sort_by_scores_ascending(&scores);
+        players_by_score.sort_by_scores_ascending(&scores, &tournament.get_pairing_numbers());
         let mut bye_player = None;
 
         let pairs = if players_by_score.odd() {
@@ -37,17 +38,16 @@ impl Round {
 
         let Some(valid_pairs) = pairs
         else {
-            println!("Warning: Was not able to find good pairing, settling with monrad pairings (potential repeats/bad matches).");
-            return Self::generate_monrad(tournament);
+            return Err(Error::PairingImpossible(PairingSystem::Dutch));
         };
 
-        Self::from_pairings(
+        Ok(Self::from_pairings(
             make_pairings(
                 &valid_pairs,
                 tournament.colour_difference_pairing()
             ),
             bye_player
-        )
+        ))
     }
 }
 
@@ -60,7 +60,7 @@ fn try_use_bye_player(bye_player: Option<PlayerID>, players_by_score: &PlayerIDL
         using_players.remove(bye);
     }
 
-    let already_played = tournament.get_already_played();
+    let allowed = tournament.pairing_allowed();
     
     let mut pairing_brackets = Vec::new();
     let mut current_score = f32::MIN;
@@ -87,18 +87,18 @@ fn try_use_bye_player(bye_player: Option<PlayerID>, players_by_score: &PlayerIDL
     // println!("BRACKETS");
     // println!("{pairing_brackets:?}");
 
-    try_pair_brackets(pairing_brackets, &already_played)
+    try_pair_brackets(pairing_brackets, &allowed)
 
 }
 
-fn try_pair_brackets(mut brackets: Vec<PlayerIDList>, already_played: &[PlayerIDList]) -> Option<Vec<(PlayerID, PlayerID)>> {
+fn try_pair_brackets(mut brackets: Vec<PlayerIDList>, allowed: &impl Fn(PlayerID, PlayerID) -> bool) -> Option<Vec<(PlayerID, PlayerID)>> {
 
     let mut move_down_players = PlayerIDList::new();
     let mut final_pairings = Vec::new();
 
     for bracket in brackets.iter() {
 
-        let (new_pairings, unpaired_players) = pair_bracket(move_down_players, bracket.clone(), &already_played);
+        let (new_pairings, unpaired_players) = pair_bracket(move_down_players, bracket.clone(), allowed);
 
         move_down_players = unpaired_players;
 
@@ -119,7 +119,7 @@ fn try_pair_brackets(mut brackets: Vec<PlayerIDList>, already_played: &[PlayerID
         let bracket2 = brackets.pop().unwrap();
 
         brackets.push(bracket1 + bracket2);
-        try_pair_brackets(brackets, already_played)
+        try_pair_brackets(brackets, allowed)
     
     }
     else {
@@ -127,7 +127,7 @@ fn try_pair_brackets(mut brackets: Vec<PlayerIDList>, already_played: &[PlayerID
     }
 }
 
-fn pair_bracket(move_down_players: PlayerIDList, resident_players: PlayerIDList, already_played: &[PlayerIDList]) -> (Vec<(PlayerID, PlayerID)>, PlayerIDList) {
+fn pair_bracket(move_down_players: PlayerIDList, resident_players: PlayerIDList, allowed: &impl Fn(PlayerID, PlayerID) -> bool) -> (Vec<(PlayerID, PlayerID)>, PlayerIDList) {
 
     if move_down_players.0.is_empty() && resident_players.0.len() == 1 {
         return (Vec::new(), resident_players);
@@ -176,7 +176,7 @@ fn pair_bracket(move_down_players: PlayerIDList, resident_players: PlayerIDList,
         
             while !s1.0.is_empty() {
         
-                if let Some(pairing) = try_create_pairing(&mut s1, &mut s2, already_played) {
+                if let Some(pairing) = try_create_pairing(&mut s1, &mut s2, allowed) {
                     accepted_pairings.push(pairing);
                 }
                 else {
@@ -201,7 +201,6 @@ fn pair_bracket(move_down_players: PlayerIDList, resident_players: PlayerIDList,
         
             // println!("{move_down_players:?}");
             // println!("{resident_players:?}");
-            // println!("{already_played:?}");
             // println!("m0: {m0}, max_pairs: {max_pairs}, m1: {m1}, n1: {n1}");
             // println!("{accepted_pairings:?}");
             // println!("{next_move_downs:?}\n");
@@ -213,13 +212,12 @@ fn pair_bracket(move_down_players: PlayerIDList, resident_players: PlayerIDList,
 
 }
 
-fn try_create_pairing(s1: &mut PlayerIDList, s2: &mut PlayerIDList, already_played: &[PlayerIDList]) -> Option<(PlayerID, PlayerID)> {
+fn try_create_pairing(s1: &mut PlayerIDList, s2: &mut PlayerIDList, allowed: &impl Fn(PlayerID, PlayerID) -> bool) -> Option<(PlayerID, PlayerID)> {
 
     let player1 = *s1.0.last().unwrap();
     let player2 = *s2.0.last().unwrap();
 
-    // add other absolute criteria
-    if !already_played[player1].0.contains(&player2) && !already_played[player2].0.contains(&player1) {
+    if allowed(player1, player2) {
 
         s1.0.pop();
         s2.0.pop();
@@ -2,8 +2,80 @@ mod monrad;
 mod dutch;
 mod danish;
 
+#[cfg(test)]
+mod tests;
+
 use crate::*;
 
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum PairingSystem {
+    #[default]
+    Dutch,
+    Monrad,
+    Danish
+}
+
+impl PairingSystem {
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "dutch" => Some(Self::Dutch),
+            "monrad" => Some(Self::Monrad),
+            "danish" => Some(Self::Danish),
+            _ => None
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Dutch => "Dutch",
+            Self::Monrad => "Monrad",
+            Self::Danish => "Danish"
+        }
+    }
+
+    pub fn fallback(self) -> Option<Self> {
+        match self {
+            Self::Dutch => Some(Self::Monrad),
+            Self::Monrad => Some(Self::Danish),
+            Self::Danish => None
+        }
+    }
+
+    /// Pairs the next round using only this system. The first round is always paired by seeding.
+    pub fn generate(self, tournament: &Tournament) -> Result<Round, Error> {
+
+        if !tournament.started() {
+            return Ok(Round::from_seeding(tournament));
+        }
+
+        match self {
+            Self::Dutch => Round::generate_dutch(tournament),
+            Self::Monrad => Round::generate_monrad(tournament),
+            Self::Danish => Round::generate_danish(tournament)
+        }
+    }
+
+    /// Tries each less strict system in turn, also returning the system that was used.
+    pub fn generate_with_fallback(self, tournament: &Tournament) -> Result<(Round, Self), Error> {
+
+        let mut system = self;
+
+        loop {
+
+            let err = match system.generate(tournament) {
+                Ok(round) => return Ok((round, system)),
+                Err(err) => err
+            };
+
+            system = system.fallback().ok_or(err)?;
+
+        }
+    }
+}
+
 impl Round {
 
     pub fn from_pairings(pairings: Vec<Pairing>, bye_player: Option<PlayerID>) -> Self {
@@ -26,10 +98,10 @@ impl Round {
 
     pub fn from_seeding(tournament: &Tournament) -> Self {
 
+        let pairing_numbers = tournament.get_pairing_numbers();
+
         let mut players_by_seeding = tournament.get_active_player_ids();
-        players_by_seeding.0.sort_by(
-            |&id1, &id2| tournament.players[id2].rating.cmp(&tournament.players[id1].rating)
-        );
+        players_by_seeding.0.sort_by_key(|&id| pairing_numbers[id]);
 
         let bye_player = (players_by_seeding.odd()).then(|| players_by_seeding.0.pop().unwrap());
 
@@ -1,23 +1,24 @@
 use crate::*;
+use super::PairingSystem;
 
-fn top_pair_valid(pairs: &mut [(usize, usize)], already_played: &[PlayerIDList]) -> bool {
+fn top_pair_valid(pairs: &mut [(usize, usize)], allowed: &impl Fn(PlayerID, PlayerID) -> bool) -> bool {
     
     let Some(&(player1, player2)) = pairs.first()
     else {
         return true;
     };
 
-    !has_already_played(player1, player2, already_played) && create_valid_pairs(&mut pairs[1..], already_played)
+    allowed(player1, player2) && create_valid_pairs(&mut pairs[1..], allowed)
     
 }
 
-fn create_valid_pairs(pairs: &mut [(usize, usize)], already_played: &[PlayerIDList]) -> bool {
+fn create_valid_pairs(pairs: &mut [(usize, usize)], allowed: &impl Fn(PlayerID, PlayerID) -> bool) -> bool {
 
     if pairs.is_empty() {
         return true;
     }
 
-    if top_pair_valid(pairs, already_played) {
+    if top_pair_valid(pairs, allowed) {
         return true;
     }
 
@@ -31,7 +32,7 @@ fn create_valid_pairs(pairs: &mut [(usize, usize)], already_played: &[PlayerIDLi
         let mut swap_b = pairs[idx].0;
 
         (pairs[0].0, pairs[idx].0) = (swap_b, swap_a);
-        if top_pair_valid(pairs, already_played) {
+        if top_pair_valid(pairs, allowed) {
             return true;
         }
         (pairs[0].0, pairs[idx].0) = (swap_a, swap_b);
@@ -39,7 +40,7 @@ fn create_valid_pairs(pairs: &mut [(usize, usize)], already_played: &[PlayerIDLi
         swap_b = pairs[idx].1;
 
         (pairs[0].0, pairs[idx].1) = (swap_b, swap_a);
-        if top_pair_valid(pairs, already_played) {
+        if top_pair_valid(pairs, allowed) {
             return true;
         }
         (pairs[0].0, pairs[idx].1) = (swap_a, swap_b);
@@ -50,7 +51,7 @@ fn create_valid_pairs(pairs: &mut [(usize, usize)], already_played: &[PlayerIDLi
 
 }
 
-fn try_use_bye_player(bye_player: Option<PlayerID>, players_by_score: &PlayerIDList, already_played: &[PlayerIDList]) -> Option<Vec<(PlayerID, PlayerID)>> {
+fn try_use_bye_player(bye_player: Option<PlayerID>, players_by_score: &PlayerIDList, allowed: &impl Fn(PlayerID, PlayerID) -> bool) -> Option<Vec<(PlayerID, PlayerID)>> {
 
     let mut using_players = players_by_score.clone();
     if let Some(bye) = bye_player {
@@ -59,21 +60,22 @@ fn try_use_bye_player(bye_player: Option<PlayerID>, players_by_score: &PlayerIDL
 
     let mut pairs = using_players.pair_off_in_order();
 
-    create_valid_pairs(&mut pairs, already_played).then_some(pairs)
+    create_valid_pairs(&mut pairs, allowed).then_some(pairs)
 
 }
 
 impl Round {
 
-    pub fn generate_monrad(tournament: &Tournament) -> Self {
+    pub fn generate_monrad(tournament: &Tournament) -> Result<Self, Error> {
+
         let scores = tournament.get_player_scores();
         
         let mut players_by_score = tournament.get_active_player_ids();
-        players_by_score.sort_by_scores_ascending(&scores);
+        players_by_score.sort_by_scores_ascending(&scores, &tournament.get_pairing_numbers());
         players_by_score.0.reverse();
         let mut bye_player = None;
 
-        let already_played = tournament.get_already_played();
+        let allowed = tournament.pairing_allowed();
 
         let pairs = if players_by_score.odd() {
         
@@ -85,7 +87,7 @@ impl Round {
             for bye in bye_players {
 
                 bye_player = Some(bye);
-                result_pairs = try_use_bye_player(bye_player, &players_by_score, &already_played);
+                result_pairs = try_use_bye_player(bye_player, &players_by_score, &allowed);
 
                 if result_pairs.is_some() {
                     break;
@@ -96,21 +98,20 @@ impl Round {
 
         }
         else {
-            try_use_bye_player(None, &players_by_score, &already_played)
+            try_use_bye_player(None, &players_by_score, &allowed)
         };
 
         let Some(valid_pairs) = pairs
         else {
-            println!("Warning: Was not able to find pairing without repeats, had to settle with having at least one.");
-            return Self::generate_danish(tournament);
+            return Err(Error::PairingImpossible(PairingSystem::Monrad));
         };
 
-        Self::from_pairings(
+        Ok(Self::from_pairings(
             make_pairings(
                 &valid_pairs,
                 tournament.colour_difference_pairing()
             ),
             bye_player
-        )
+        ))
     }
 }
\ No newline at end of file
@@ -0,0 +1,246 @@
+use crate::*;
+use crate::algorithms::PairingSystem;
+use crate::checker::Violation;
+use crate::testing::played_tournament;
+
+use rand::Rng;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const CASES: u64 = 200;
+
+// plays out random tournaments, handing each round to `check` along with the tournament state before it
+fn for_each_random_round(system: PairingSystem, mut check: impl FnMut(u64, &Tournament, &Round)) {
+
+    for seed in 0..CASES {
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let num_players = rng.gen_range(4..=9);
+        let num_rounds = rng.gen_range(1..num_players);
+
+        let mut tournament = Tournament::new();
+
+        for idx in 0..num_players {
+            tournament.add_player(format!("Player {}", idx + 1), rng.gen_bool(0.8).then(|| rng.gen_range(1000..=2400)));
+        }
+
+        for _ in 0..num_rounds {
+
+            if tournament.get_active_player_ids().0.len() > 3 && rng.gen_bool(0.1) {
+                let withdrawn = rng.gen_range(0..num_players);
+                tournament.players[withdrawn].active = false;
+            }
+
+            let (mut round, _) = system.generate_with_fallback(&tournament).unwrap();
+            check(seed, &tournament, &round);
+
+            round.simulate_results(&tournament.players, &mut rng);
+            tournament.rounds.push(round);
+
+        }
+    }
+}
+
+fn structural_violations(tournament: &Tournament, round: &Round) -> Vec<String> {
+    round.check(tournament, None)
+        .iter()
+        .filter(|violation| matches!(
+            violation,
+            Violation::UnknownPlayer(_) |
+            Violation::InactivePlayer(_) |
+            Violation::PairedWithSelf(_) |
+            Violation::PairedTwice(_) |
+            Violation::MissingPlayer(_) |
+            Violation::SecondBye(_)
+        ))
+        .map(|violation| violation.as_string(&tournament.players))
+        .collect()
+}
+
+fn rule_violations(tournament: &Tournament, round: &Round) -> Vec<String> {
+    round.check(tournament, None)
+        .iter()
+        .map(|violation| violation.as_string(&tournament.players))
+        .collect()
+}
+
+// exhaustive search for a pairing that meets the absolute criteria
+fn valid_pairing_exists(tournament: &Tournament) -> bool {
+
+    let allowed = tournament.pairing_allowed();
+    let active_players = tournament.get_active_player_ids();
+
+    let bye_options = if active_players.odd() {
+        active_players.get_players_without_bye(tournament)
+            .into_iter()
+            .map(Some)
+            .collect()
+    }
+    else {
+        vec![None]
+    };
+
+    bye_options.into_iter().any(|bye_player| {
+
+        let mut remaining = active_players.clone();
+        if let Some(bye) = bye_player {
+            remaining.remove(bye);
+        }
+
+        perfect_matching_exists(&remaining.0, &allowed)
+
+    })
+}
+
+fn perfect_matching_exists(players: &[PlayerID], allowed: &impl Fn(PlayerID, PlayerID) -> bool) -> bool {
+
+    let Some((&first, rest)) = players.split_first()
+    else {
+        return true;
+    };
+
+    rest.iter().enumerate().any(|(idx, &opponent)| {
+
+        let mut remaining = rest.to_vec();
+        remaining.remove(idx);
+
+        allowed(first, opponent) && perfect_matching_exists(&remaining, allowed)
+
+    })
+}
+
+fn assert_strict_system(system: PairingSystem) {
+    for_each_random_round(system, |seed, tournament, round| {
+
+        let violations = structural_violations(tournament, round);
+        assert!(violations.is_empty(), "seed {seed}, round {}: {violations:?}", tournament.rounds.len() + 1);
+
+        if !tournament.started() {
+            return;
+        }
+
+        match system.generate(tournament) {
+            Ok(strict_round) => {
+                let violations = rule_violations(tournament, &strict_round);
+                assert!(violations.is_empty(), "seed {seed}, round {}: {violations:?}", tournament.rounds.len() + 1);
+            }
+            Err(_) => assert!(
+                !valid_pairing_exists(tournament),
+                "seed {seed}, round {}: {} fell back although a valid pairing exists",
+                tournament.rounds.len() + 1,
+                system.name()
+            )
+        }
+    });
+}
+
+#[test]
+fn dutch_follows_pairing_rules() {
+    assert_strict_system(PairingSystem::Dutch);
+}
+
+#[test]
+fn monrad_follows_pairing_rules() {
+    assert_strict_system(PairingSystem::Monrad);
+}
+
+#[test]
+fn danish_pairs_every_player_once() {
+    for_each_random_round(PairingSystem::Danish, |seed, tournament, round| {
+        let violations = structural_violations(tournament, round);
+        assert!(violations.is_empty(), "seed {seed}, round {}: {violations:?}", tournament.rounds.len() + 1);
+    });
+}
+
+// the Dutch pairings of the next round by pairing number, lower number first and in order, and the bye
+fn dutch_pairings(tournament: &Tournament) -> (Vec<(u32, u32)>, Option<u32>) {
+
+    let round = PairingSystem::Dutch.generate(tournament).unwrap();
+    let numbers = tournament.get_pairing_numbers();
+
+    let mut pairs: Vec<_> = round.games.iter()
+        .map(|game| {
+            let (white, black) = (numbers[game.white_player], numbers[game.black_player]);
+            (white.min(black), white.max(black))
+        })
+        .collect();
+
+    pairs.sort();
+
+    (pairs, round.bye_player.map(|player| numbers[player]))
+
+}
+
+#[test]
+fn dutch_pairs_top_half_against_bottom_half() {
+
+    let tournament = played_tournament(8, &[(&[(1, 5, GameResult::Win), (6, 2, GameResult::Loss), (3, 7, GameResult::Win), (8, 4, GameResult::Loss)], None)]);
+
+    assert_eq!(dutch_pairings(&tournament), (vec![(1, 3), (2, 4), (5, 7), (6, 8)], None));
+
+}
+
+#[test]
+fn dutch_transposes_to_avoid_rematches() {
+
+    // everyone is on ½, and 1-3 and 2-4 already played
+    let tournament = played_tournament(4, &[(&[(1, 3, GameResult::Draw), (4, 2, GameResult::Draw)], None)]);
+
+    assert_eq!(dutch_pairings(&tournament), (vec![(1, 4), (2, 3)], None));
+
+}
+
+#[test]
+fn dutch_floats_players_who_cannot_be_paired_within_their_score() {
+
+    // 1 and 2 lead on 1½ but have played each other, as have 3 and 4
+    let tournament = played_tournament(4, &[
+        (&[(1, 3, GameResult::Win), (4, 2, GameResult::Loss)], None),
+        (&[(1, 2, GameResult::Draw), (3, 4, GameResult::Draw)], None)
+    ]);
+
+    assert_eq!(dutch_pairings(&tournament), (vec![(1, 4), (2, 3)], None));
+
+}
+
+#[test]
+fn dutch_gives_the_bye_to_the_lowest_player_without_one() {
+
+    // 3 is last on ½ after 4 and 5 had their byes, then 5 floats down to 2
+    let tournament = played_tournament(5, &[
+        (&[(1, 3, GameResult::Draw), (4, 2, GameResult::Loss)], Some(5)),
+        (&[(2, 1, GameResult::Draw), (3, 5, GameResult::Loss)], Some(4))
+    ]);
+
+    assert_eq!(dutch_pairings(&tournament), (vec![(1, 4), (2, 5)], Some(3)));
+
+    // only the leader hasn't had a bye
+    let tournament = played_tournament(3, &[
+        (&[(1, 2, GameResult::Win)], Some(3)),
+        (&[(1, 3, GameResult::Win)], Some(2))
+    ]);
+
+    assert_eq!(dutch_pairings(&tournament), (vec![(2, 3)], Some(1)));
+
+}
+
+#[test]
+fn topscorers_may_meet_despite_colours_in_the_final_round() {
+
+    // 1 and 2 lead on 2 after white twice each, so both are due black and can only meet as topscorers;
+    // 3 and 5 are due white after black twice
+    let mut tournament = played_tournament(6, &[
+        (&[(1, 4, GameResult::Win), (2, 5, GameResult::Win), (6, 3, GameResult::Win)], None),
+        (&[(1, 6, GameResult::Win), (2, 3, GameResult::Win), (4, 5, GameResult::Draw)], None)
+    ]);
+
+    assert_eq!(dutch_pairings(&tournament).0, [(1, 5), (2, 6), (3, 4)]);
+
+    tournament.total_rounds = Some(3);
+    assert_eq!(dutch_pairings(&tournament).0, [(1, 2), (3, 4), (5, 6)]);
+
+    let round = PairingSystem::Dutch.generate(&tournament).unwrap();
+    let violations = rule_violations(&tournament, &round);
+    assert!(violations.is_empty(), "{violations:?}");
+
+}
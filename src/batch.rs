@@ -0,0 +1,191 @@
+use swiss::*;
+
+use crate::{run_command, Input, Session};
+
+pub struct BatchOptions {
+    pub dry_run: bool,
+    pub keep_going: bool
+}
+
+/// Runs a script of prompt commands, returning how many of them failed.
+/// Changes are only applied if the script completes (or `keep_going` is set) and this isn't a dry run.
+pub fn run_script(session: &mut Session, script: &str, options: &BatchOptions) -> usize {
+
+    let mut working_copy = session.clone();
+    let mut input = Input::script(script, options.dry_run);
+    let mut failures = 0;
+
+    while !input.finished() {
+
+        let Ok(command) = input.read_line("> ", false)
+        else {
+            break;
+        };
+
+        if command.is_empty() || command.starts_with('#') {
+            continue;
+        }
+
+        let line_number = input.line_number();
+
+        if let Err(err) = run_command(&mut working_copy, &command, &mut input) {
+
+            println!("Error: {err} (line {line_number}).");
+            failures += 1;
+
+            if !options.keep_going {
+                println!("Script stopped, no changes were made.");
+                return failures;
+            }
+        }
+    }
+
+    if options.dry_run {
+
+        let changes = describe_event_changes(&session.event, &working_copy.event);
+
+        if changes.is_empty() {
+            println!("Dry run: no changes would be made.");
+        }
+        else {
+            println!("Dry run: the following changes would be made:");
+        }
+
+        for change in changes {
+            println!("- {change}");
+        }
+    }
+    else {
+        *session = working_copy;
+    }
+
+    failures
+
+}
+
+fn describe_event_changes(before: &Event, after: &Event) -> Vec<String> {
+
+    let mut changes = Vec::new();
+
+    if before.name != after.name {
+        changes.push(format!("Event name changed to \"{}\"", after.name));
+    }
+
+    for (idx, section) in after.sections.iter().enumerate() {
+
+        let old_tournament = match before.sections.get(idx) {
+            Some(old_section) => old_section.tournament.clone(),
+            None => {
+
+                changes.push(format!("Add section {}", section.description()));
+
+                Tournament {
+                    name: section.tournament.name.clone(),
+                    ..Tournament::new()
+                }
+            }
+        };
+
+        let section_changes = describe_changes(&old_tournament, &section.tournament);
+
+        // only name the section when there is more than one
+        if after.sections.len() > 1 {
+            changes.extend(section_changes.into_iter().map(|change| format!("{}: {change}", old_tournament.name)));
+        }
+        else {
+            changes.extend(section_changes);
+        }
+    }
+
+    changes
+
+}
+
+fn describe_changes(before: &Tournament, after: &Tournament) -> Vec<String> {
+
+    let mut changes = Vec::new();
+
+    if before.name != after.name {
+        changes.push(format!("Tournament name changed to \"{}\"", after.name));
+    }
+
+    if before.system != after.system {
+        changes.push(format!("Pairing system changed from {} to {}", before.system.name(), after.system.name()));
+    }
+
+    if before.total_rounds != after.total_rounds {
+        match after.total_rounds {
+            Some(total_rounds) => changes.push(format!("Number of rounds set to {total_rounds}")),
+            None => changes.push("Number of rounds cleared".to_string())
+        }
+    }
+
+    if before.finished != after.finished {
+        changes.push(if after.finished { "Finish tournament" } else { "Reopen tournament" }.to_string());
+    }
+
+    if before.board_rules != after.board_rules {
+        changes.push("Board rules changed".to_string());
+    }
+
+    if before.categories != after.categories {
+
+        let names: Vec<_> = after.categories.iter()
+            .map(|category| category.name())
+            .collect();
+
+        changes.push(format!("Categories changed to [{}]", names.join(", ")));
+
+    }
+
+    for (id, player) in after.players.iter().enumerate() {
+        match before.players.get(id) {
+            None => changes.push(format!("Add player {}", player.label())),
+            Some(old_player) if old_player.active && !player.active => changes.push(format!("Withdraw {}", player.label())),
+            Some(old_player) if old_player.sex != player.sex || old_player.birth_year != player.birth_year || old_player.title != player.title => {
+                changes.push(format!("Update details of {}", player.label()));
+            }
+            _ => {}
+        }
+    }
+
+    for (idx, round) in after.rounds.iter().enumerate() {
+
+        let Some(old_round) = before.rounds.get(idx)
+        else {
+            changes.push(format!("Pair round {} ({} boards)", idx + 1, round.games.len()));
+            continue;
+        };
+
+        if !round.same_pairings(old_round) {
+            changes.push(format!("Replace pairings of round {}", idx + 1));
+            continue;
+        }
+
+        for game in round.games.iter() {
+
+            let old_result = old_round.games.iter()
+                .find(|old_game| old_game.white_player == game.white_player && old_game.black_player == game.black_player)
+                .map(|old_game| old_game.result);
+
+            if old_result != Some(game.result) {
+                changes.push(format!(
+                    "Round {} board {}: {} - {} {} -> {}",
+                    idx + 1,
+                    game.board_number,
+                    after.players[game.white_player].label(),
+                    after.players[game.black_player].label(),
+                    old_result.map_or("?", GameResult::as_notation),
+                    game.result.as_notation()
+                ));
+            }
+        }
+    }
+
+    for idx in after.rounds.len()..before.rounds.len() {
+        changes.push(format!("Remove round {}", idx + 1));
+    }
+
+    changes
+
+}
@@ -0,0 +1,249 @@
+use crate::*;
+
+use serde::{Deserialize, Serialize};
+
+/// Exceptions to the usual board order, applied whenever a round is numbered.
+#[derive(Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct BoardRules {
+    /// Where the boards start, so every section of an event keeps its own block of board numbers.
+    /// Missing from files saved before sections had their own boards.
+    #[serde(default)]
+    pub first_board: Option<u32>,
+    /// Players who always play at the same board, e.g. for wheelchair access.
+    /// In an event the board number is shared by every section.
+    pub fixed: Vec<(PlayerID, u32)>,
+    /// Players whose games take the top boards of their section, e.g. for a live broadcast.
+    pub pinned: Vec<PlayerID>
+}
+
+impl BoardRules {
+
+    /// Board 1 unless set.
+    pub fn first_board(&self) -> u32 {
+        self.first_board.unwrap_or(1)
+    }
+
+    pub fn fixed_board(&self, player: PlayerID) -> Option<u32> {
+        self.fixed.iter()
+            .find(|(fixed_player, _)| *fixed_player == player)
+            .map(|(_, board)| *board)
+    }
+
+    /// Fixes the player to a board, replacing any earlier rule for them.
+    pub fn fix(&mut self, player: PlayerID, board: u32) {
+        self.clear(player);
+        self.fixed.push((player, board));
+    }
+
+    /// Puts the player's games on the top boards, replacing any earlier rule for them.
+    pub fn pin(&mut self, player: PlayerID) {
+        self.clear(player);
+        self.pinned.push(player);
+    }
+
+    pub fn clear(&mut self, player: PlayerID) {
+        self.fixed.retain(|(fixed_player, _)| *fixed_player != player);
+        self.pinned.retain(|&pinned_player| pinned_player != player);
+    }
+
+    /// One line per rule.
+    pub fn as_string(&self, players: &[Player]) -> String {
+
+        let mut output = String::new();
+
+        for &(player, board) in self.fixed.iter() {
+            output += &format!("{}: board {board}\n", players[player].label());
+        }
+
+        for &player in self.pinned.iter() {
+            output += &format!("{}: top boards\n", players[player].label());
+        }
+
+        if output.is_empty() {
+            output += "No board rules.\n";
+        }
+
+        output
+
+    }
+}
+
+impl Tournament {
+
+    /// Scores from the rounds before the round (0-indexed), which its boards are ordered by.
+    pub fn get_player_scores_before(&self, round_idx: usize) -> Vec<f32> {
+
+        let mut scores = vec![0.0; self.players.len()];
+
+        for round in self.rounds.iter().take(round_idx) {
+
+            if let Some(bye_player) = round.bye_player {
+                scores[bye_player] += GameResult::Win.score();
+            }
+
+            for game in round.games.iter() {
+                scores[game.white_player] += game.result.score();
+                scores[game.black_player] += game.result.opposite().score();
+            }
+        }
+
+        scores
+
+    }
+
+    /// Numbers the boards of a round (0-indexed) from `first_board`, skipping `reserved` boards, and returns the next free number.
+    /// Games of pinned players come first, then boards follow FIDE order: the higher score of the two players,
+    /// then their combined score, then the lower pairing number. Fixed players keep their board if it is still free.
+    pub fn number_boards(&mut self, round_idx: usize, first_board: u32, reserved: &[u32]) -> u32 {
+
+        let scores = self.get_player_scores_before(round_idx);
+
+        let numbers: Vec<_> = self.players.iter()
+            .map(|player| player.pairing_number.unwrap_or(u32::MAX))
+            .collect();
+
+        let rules = &self.board_rules;
+
+        let Some(round) = self.rounds.get_mut(round_idx)
+        else {
+            return first_board;
+        };
+
+        let order_key = |game: &Game| {
+
+            let (white, black) = (game.white_player, game.black_player);
+            let pinned = rules.pinned.contains(&white) || rules.pinned.contains(&black);
+
+            (pinned, scores[white].max(scores[black]), scores[white] + scores[black], numbers[white].min(numbers[black]))
+
+        };
+
+        round.games.sort_by(|game1, game2| {
+
+            let (pinned1, max1, sum1, number1) = order_key(game1);
+            let (pinned2, max2, sum2, number2) = order_key(game2);
+
+            pinned2.cmp(&pinned1)
+                .then(max2.total_cmp(&max1))
+                .then(sum2.total_cmp(&sum1))
+                .then(number1.cmp(&number2))
+
+        });
+
+        let mut taken = reserved.to_vec();
+        let mut fixed = vec![false; round.games.len()];
+
+        for (idx, game) in round.games.iter_mut().enumerate() {
+
+            let board = rules.fixed_board(game.white_player)
+                .into_iter()
+                .chain(rules.fixed_board(game.black_player))
+                .find(|board| !taken.contains(board));
+
+            if let Some(board) = board {
+                game.board_number = board;
+                taken.push(board);
+                fixed[idx] = true;
+            }
+        }
+
+        let mut next_board = first_board;
+
+        for (game, _) in round.games.iter_mut().zip(fixed).filter(|(_, fixed)| !fixed) {
+
+            while taken.contains(&next_board) {
+                next_board += 1;
+            }
+
+            game.board_number = next_board;
+            next_board += 1;
+
+        }
+
+        round.games.sort_by_key(|game| game.board_number);
+
+        next_board
+
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+    use crate::testing::played_tournament;
+
+    // round 1 played as 1-5 1-0, 6-2 ½-½, 3-7 1-0, 8-4 1-0 and round 2 paired as 4-5, 2-6, 7-1, 3-8,
+    // both given out of board order
+    fn tournament() -> Tournament {
+        played_tournament(8, &[
+            (&[(3, 7, GameResult::Win), (8, 4, GameResult::Win), (1, 5, GameResult::Win), (6, 2, GameResult::Draw)], None),
+            (&[(4, 5, GameResult::Pending), (2, 6, GameResult::Pending), (7, 1, GameResult::Pending), (3, 8, GameResult::Pending)], None)
+        ])
+    }
+
+    // (board, white, black) by pairing number
+    fn boards(tournament: &Tournament, round_idx: usize) -> Vec<(u32, PlayerID, PlayerID)> {
+        tournament.rounds[round_idx].games.iter()
+            .map(|game| (game.board_number, game.white_player + 1, game.black_player + 1))
+            .collect()
+    }
+
+    #[test]
+    fn boards_follow_highest_score_then_combined_score_then_pairing_number() {
+
+        let mut tournament = tournament();
+
+        assert_eq!(tournament.number_boards(0, 1, &[]), 5);
+        assert_eq!(boards(&tournament, 0), [(1, 1, 5), (2, 6, 2), (3, 3, 7), (4, 8, 4)]);
+
+        tournament.number_boards(1, 1, &[]);
+        assert_eq!(boards(&tournament, 1), [(1, 3, 8), (2, 7, 1), (3, 2, 6), (4, 4, 5)]);
+
+    }
+
+    #[test]
+    fn pinned_players_take_the_top_boards() {
+
+        let mut tournament = tournament();
+        // player 5
+        tournament.board_rules.pin(4);
+
+        tournament.number_boards(1, 1, &[]);
+        assert_eq!(boards(&tournament, 1), [(1, 4, 5), (2, 3, 8), (3, 7, 1), (4, 2, 6)]);
+
+    }
+
+    #[test]
+    fn fixed_players_keep_their_board_and_others_skip_it() {
+
+        let mut tournament = tournament();
+        // player 4 at board 3
+        tournament.board_rules.fix(3, 3);
+
+        tournament.number_boards(1, 1, &[]);
+        assert_eq!(boards(&tournament, 1), [(1, 3, 8), (2, 7, 1), (3, 4, 5), (4, 2, 6)]);
+
+        // a fixed board used elsewhere in the event can't be had
+        assert_eq!(tournament.number_boards(1, 11, &[3, 12]), 16);
+        assert_eq!(boards(&tournament, 1), [(11, 3, 8), (13, 7, 1), (14, 2, 6), (15, 4, 5)]);
+
+    }
+
+    #[test]
+    fn rounds_are_numbered_from_the_first_board() {
+
+        let mut tournament = Tournament::new();
+        tournament.board_rules.first_board = Some(101);
+
+        for name in ["Ann", "Bob", "Cid", "Dee"] {
+            tournament.add_player(name.to_string(), None);
+        }
+
+        tournament.pair_next_round().unwrap();
+
+        let board_numbers: Vec<_> = tournament.rounds[0].games.iter().map(|game| game.board_number).collect();
+        assert_eq!(board_numbers, [101, 102]);
+
+    }
+}
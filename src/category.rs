@@ -0,0 +1,169 @@
+use crate::*;
+
+use serde::{Deserialize, Serialize};
+
+/// A group of players that can have its own ranking and prizes, based on rating, age or sex.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Category {
+    /// Rated below the limit, e.g. `U1600`; unrated players are not included.
+    RatedBelow(u32),
+    Unrated,
+    /// Younger than the age at the start of the year, e.g. `U18`.
+    AgeBelow(u32),
+    /// At least the age at the start of the year, e.g. `S65`.
+    AgeAtLeast(u32),
+    Women
+}
+
+impl Category {
+
+    /// Parses names like `U1600`, `U18`, `S65`, `unrated` and `women`; limits below 100 are ages.
+    pub fn from_name(name: &str) -> Option<Self> {
+
+        let name = name.trim().to_lowercase();
+
+        match name.as_str() {
+            "unrated" => return Some(Self::Unrated),
+            "women" | "woman" | "w" | "female" => return Some(Self::Women),
+            _ => {}
+        }
+
+        if let Some(limit) = name.strip_prefix('u').and_then(|limit| limit.parse::<u32>().ok()) {
+            return Some(if limit < 100 { Self::AgeBelow(limit) } else { Self::RatedBelow(limit) });
+        }
+
+        name.strip_prefix('s')
+            .and_then(|age| age.parse().ok())
+            .map(Self::AgeAtLeast)
+
+    }
+
+    pub fn name(self) -> String {
+        match self {
+            Self::RatedBelow(limit) | Self::AgeBelow(limit) => format!("U{limit}"),
+            Self::Unrated => "Unrated".to_string(),
+            Self::AgeAtLeast(age) => format!("S{age}"),
+            Self::Women => "Women".to_string()
+        }
+    }
+
+    /// Ages are counted as `year` minus the birth year, players with unknown details never match.
+    pub fn includes(self, player: &Player, year: u32) -> bool {
+
+        let age = player.birth_year.map(|birth_year| year.saturating_sub(birth_year));
+
+        match self {
+            Self::RatedBelow(limit) => player.rating.is_some_and(|rating| rating < limit),
+            Self::Unrated => player.rating.is_none(),
+            Self::AgeBelow(limit) => age.is_some_and(|age| age < limit),
+            Self::AgeAtLeast(limit) => age.is_some_and(|age| age >= limit),
+            Self::Women => player.sex == Some(Sex::Female)
+        }
+    }
+}
+
+/// A row of the standings of one category.
+pub struct CategoryStanding {
+    /// Within the category, shared by players tied on score and Sonneborn-Berger.
+    pub place: usize,
+    /// Its place is the overall one.
+    pub standing: Standing
+}
+
+impl Tournament {
+
+    /// The year ages are counted against: the one the tournament started in, or the current year before it starts.
+    pub fn year(&self) -> u32 {
+        self.year.unwrap_or_else(current_year)
+    }
+
+    /// The standings of the players in the category, counting ages against `year`.
+    pub fn category_standings(&self, category: Category, year: u32) -> Vec<CategoryStanding> {
+
+        let mut placing = 0;
+        let mut prev_place = 0;
+
+        self.standings().into_iter()
+            .filter(|standing| category.includes(&self.players[standing.player], year))
+            .enumerate()
+            .map(|(idx, standing)| {
+
+                // overall places are already shared by exactly the players tied on score and Sonneborn-Berger
+                if standing.place != prev_place {
+                    placing = idx + 1;
+                    prev_place = standing.place;
+                }
+
+                CategoryStanding {
+                    place: placing,
+                    standing
+                }
+            })
+            .collect()
+    }
+}
+
+/// The current calendar year (UTC), which ages are counted against.
+pub fn current_year() -> u32 {
+    utc_date(unix_time()).0
+}
+
+/// Seconds since the Unix epoch.
+pub fn unix_time() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// The UTC calendar date of a Unix time as `(year, month, day)`.
+pub fn utc_date(seconds: u64) -> (u32, u32, u32) {
+
+    // days to civil date, from Howard Hinnant's date algorithms
+    let days = (seconds / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year as u32, month as u32, day as u32)
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    fn ages_are_counted_against_the_year_the_tournament_started() {
+
+        let mut tournament = Tournament::new();
+
+        for (name, birth_year) in [("Ann", 2006), ("Bob", 2007)] {
+            tournament.add_player(name.to_string(), None);
+            tournament.players.last_mut().unwrap().birth_year = Some(birth_year);
+        }
+
+        tournament.year = Some(2024);
+        tournament.pair_next_round().unwrap();
+        assert_eq!(tournament.year, Some(2024));
+
+        let names: Vec<_> = tournament.category_standings(Category::AgeBelow(18), tournament.year()).iter()
+            .map(|category_standing| tournament.players[category_standing.standing.player].name.as_str())
+            .collect();
+
+        assert_eq!(names, ["Bob"]);
+
+        // a tournament paired without a year takes the current one
+        let mut tournament = Tournament::new();
+        tournament.add_player("Cid".to_string(), None);
+        tournament.pair_next_round().unwrap();
+        assert_eq!(tournament.year, Some(category::current_year()));
+
+    }
+}
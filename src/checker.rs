@@ -0,0 +1,167 @@
+use crate::*;
+use crate::algorithms::PairingSystem;
+
+/// A pairing rule broken by a proposed round.
+pub enum Violation {
+    UnknownPlayer(PlayerID),
+    InactivePlayer(PlayerID),
+    PairedWithSelf(PlayerID),
+    PairedTwice(PlayerID),
+    MissingPlayer(PlayerID),
+    RepeatedOpponents(PlayerID, PlayerID),
+    SecondBye(PlayerID),
+    ColourDifference(PlayerID, i32),
+    SameColourThreeTimes(PlayerID),
+    DiffersFromEngine(PairingSystem)
+}
+
+impl Violation {
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::UnknownPlayer(_) => "Unknown player",
+            Self::InactivePlayer(_) => "Withdrawn player paired",
+            Self::PairedWithSelf(_) => "Player paired against themselves",
+            Self::PairedTwice(_) => "Player paired twice",
+            Self::MissingPlayer(_) => "Player not paired",
+            Self::RepeatedOpponents(..) => "Repeated opponents",
+            Self::SecondBye(_) => "Second bye",
+            Self::ColourDifference(..) => "Colour difference above 2",
+            Self::SameColourThreeTimes(_) => "Same colour three times in a row",
+            Self::DiffersFromEngine(_) => "Differs from engine"
+        }
+    }
+
+    pub fn as_string(&self, players: &[Player]) -> String {
+
+        let name = |id: PlayerID| players.get(id)
+            .map(Player::label)
+            .unwrap_or_else(|| format!("player {id}"));
+
+        match *self {
+            Self::UnknownPlayer(id) => format!("Unknown player {}", name(id)),
+            Self::InactivePlayer(id) => format!("{} has withdrawn but is paired", name(id)),
+            Self::PairedWithSelf(id) => format!("{} is paired against themselves", name(id)),
+            Self::PairedTwice(id) => format!("{} is paired more than once", name(id)),
+            Self::MissingPlayer(id) => format!("{} is not paired", name(id)),
+            Self::RepeatedOpponents(id1, id2) => format!("{} and {} have already played", name(id1), name(id2)),
+            Self::SecondBye(id) => format!("{} already had a bye", name(id)),
+            Self::ColourDifference(id, difference) => format!("{} would have a colour difference of {difference:+}", name(id)),
+            Self::SameColourThreeTimes(id) => format!("{} would have the same colour three times in a row", name(id)),
+            Self::DiffersFromEngine(system) => format!("Round differs from {} pairings", system.name())
+        }
+    }
+}
+
+impl Round {
+
+    /// Checks this round as if it were the next round of the tournament.
+    /// If a system is given, the round is also compared against what that system would produce, or its fallback if it can't pair.
+    pub fn check(&self, tournament: &Tournament, system: Option<PairingSystem>) -> Vec<Violation> {
+
+        let mut violations = Vec::new();
+        let mut times_paired = vec![0; tournament.players.len()];
+
+        let paired_players = self.games.iter()
+            .flat_map(|game| [game.white_player, game.black_player])
+            .chain(self.bye_player);
+
+        for id in paired_players {
+
+            if id >= tournament.players.len() {
+                violations.push(Violation::UnknownPlayer(id));
+                continue;
+            }
+
+            times_paired[id] += 1;
+
+            if times_paired[id] == 2 {
+                violations.push(Violation::PairedTwice(id));
+            }
+        }
+
+        for id in tournament.get_all_player_ids().0 {
+            if tournament.players[id].active && times_paired[id] == 0 {
+                violations.push(Violation::MissingPlayer(id));
+            }
+            else if !tournament.players[id].active && times_paired[id] > 0 {
+                violations.push(Violation::InactivePlayer(id));
+            }
+        }
+
+        let already_played = tournament.get_already_played();
+        let mut colour_histories = tournament.get_colour_histories();
+        let topscorers = tournament.get_topscorers();
+
+        for game in self.games.iter() {
+
+            let (white, black) = (game.white_player, game.black_player);
+
+            if white >= tournament.players.len() || black >= tournament.players.len() {
+                continue;
+            }
+
+            if white == black {
+                violations.push(Violation::PairedWithSelf(white));
+                continue;
+            }
+
+            if has_already_played(white, black, &already_played) {
+                violations.push(Violation::RepeatedOpponents(white, black));
+            }
+
+            colour_histories[white].push(1);
+            colour_histories[black].push(-1);
+
+            // colour rules don't apply to topscorers in the final round
+            for id in [white, black].into_iter().filter(|&id| !topscorers[id]) {
+
+                let history = &colour_histories[id];
+                let difference: i32 = history.iter().sum();
+
+                if difference.abs() > 2 {
+                    violations.push(Violation::ColourDifference(id, difference));
+                }
+
+                if history.len() >= 3 && history[history.len() - 3..].iter().all(|&colour| colour == history[history.len() - 1]) {
+                    violations.push(Violation::SameColourThreeTimes(id));
+                }
+            }
+        }
+
+        if let Some(bye_player) = self.bye_player {
+            if tournament.get_bye_players().contains(&bye_player) {
+                violations.push(Violation::SecondBye(bye_player));
+            }
+        }
+
+        if let Some(system) = system {
+            if let Ok((engine_round, used_system)) = system.generate_with_fallback(tournament) {
+                if !self.same_pairings(&engine_round) {
+                    violations.push(Violation::DiffersFromEngine(used_system));
+                }
+            }
+        }
+
+        violations
+
+    }
+
+    /// Compares opponents and byes, ignoring colours and board order.
+    pub fn same_pairings(&self, other: &Round) -> bool {
+
+        let sorted_pairs = |round: &Round| {
+
+            let mut pairs: Vec<_> = round.games.iter()
+                .map(|game| (game.white_player.min(game.black_player), game.white_player.max(game.black_player)))
+                .collect();
+
+            pairs.sort();
+            pairs
+
+        };
+
+        self.bye_player == other.bye_player && sorted_pairs(self) == sorted_pairs(other)
+
+    }
+}
@@ -0,0 +1,854 @@
+use swiss::*;
+
+use crate::batch::{run_script, BatchOptions};
+use crate::Session;
+
+const USAGE: &str = "Usage:
+  swiss [players file]                          start the interactive prompt
+  swiss new <event> [players file] [--system <system>] [--name <name>] [--rounds <count>]
+  swiss section <event> <name> [--min-rating <rating>] [--max-rating <rating>] [--system <system>] [--first-board <board>]
+  swiss add <event> <name> [rating] [--sex <m/f>] [--born <year>] [--title <title>]
+  swiss remove <event> <name>
+  swiss pair <event> [--system <system>] [--alphabetical] [--format <format>]
+  swiss result <event> <board> <result> [--round <round>] [--by <name>]
+  swiss result <event> <board>:<result>...
+  swiss corrections <event>
+  swiss swap <event> <name> <name>
+  swiss bye <event> <name>
+  swiss colours <event> <board>
+  swiss games <event> [--alphabetical] [--format <format>]
+  swiss history <event> <name> [--format <format>]
+  swiss standings <event> [--category <category>] [--categories] [--format <format>]
+  swiss categories <event> [category...]
+  swiss crosstable <event> [--format <format>]
+  swiss prizes <event> <prize fund file>
+  swiss rounds <event> [count]
+  swiss boards <event> [<name> <board>|top|clear]
+  swiss finish <event> [--reopen [--by <name>]]
+  swiss contention <event> [rounds remaining] [--places <places>] [--format <format>]
+  swiss forecast <event> [rounds remaining] [--simulations <count>] [--prizes <prize fund file>] [--format <format>]
+  swiss run <event> <script> [--dry-run] [--keep-going]
+  swiss html <event> [output file]
+  swiss print <event> [output file] [--round <round>]
+  swiss pgn <event> [--round <round>]
+  swiss import <event> <pgn file>
+  swiss tui <event>
+  swiss serve <event> [--port <port>] [--bind <address>] [--origin <origin>]
+Commands that work on one section take --section <name> when the event has several.
+Players are named by pairing number (given when the first round is paired) or by name, part of which is enough if only one player matches.
+The rounds remaining default to those left of the planned number of rounds.
+Board rules apply from the next round paired. Each section numbers its boards from its first board, 1, 101, 201 and so on unless given.
+Results: 1-0, ½-½ (or 1/2, =), 0-1, +/- and -/+ for forfeits, 0-0 for a double forfeit, * to unreport.
+Results of earlier rounds are corrected with --round and logged with --by (default $USER, required if it is not set), as is reopening.
+Formats: text, csv, json, html
+Categories: U<rating>, U<age>, S<age>, unrated, women";
+
+const SUBCOMMANDS: [&str; 29] = ["new", "section", "add", "remove", "pair", "result", "corrections", "swap", "bye", "colours", "games", "history", "standings", "categories", "crosstable", "prizes", "rounds", "boards", "finish", "contention", "forecast", "html", "print", "pgn", "import", "run", "tui", "serve", "help"];
+
+pub const DEFAULT_SIMULATIONS: usize = 1000;
+
+const FLAGS: [&str; 5] = ["dry-run", "keep-going", "alphabetical", "categories", "reopen"];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Csv,
+    Json,
+    Html
+}
+
+impl Format {
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "text" => Some(Self::Text),
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            "html" => Some(Self::Html),
+            _ => None
+        }
+    }
+}
+
+struct Arguments {
+    positional: Vec<String>,
+    options: Vec<(String, String)>,
+    flags: Vec<String>
+}
+
+impl Arguments {
+
+    fn parse(args: &[String]) -> Result<Self, Error> {
+
+        let mut positional = Vec::new();
+        let mut options = Vec::new();
+        let mut flags = Vec::new();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            if let Some(flag) = arg.strip_prefix("--").filter(|flag| FLAGS.contains(flag)) {
+                flags.push(flag.to_string());
+            }
+            else if let Some(option) = arg.strip_prefix("--") {
+
+                let Some(value) = args.next()
+                else {
+                    return Err(Error::InvalidCommand(format!("Missing value for --{option}")));
+                };
+
+                options.push((option.to_string(), value.clone()));
+
+            }
+            else {
+                positional.push(arg.clone());
+            }
+        }
+
+        Ok(Self {
+            positional,
+            options,
+            flags
+        })
+    }
+
+    fn get(&self, idx: usize, name: &str) -> Result<&str, Error> {
+        self.positional.get(idx)
+            .map(|arg| arg.as_str())
+            .ok_or_else(|| Error::InvalidCommand(format!("Missing argument <{name}>")))
+    }
+
+    fn option(&self, name: &str) -> Option<&str> {
+        self.options.iter()
+            .find(|(option, _)| option == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|flag| flag == name)
+    }
+
+    fn format(&self) -> Result<Format, Error> {
+        match self.option("format") {
+            Some(name) => Format::from_name(name).ok_or_else(|| Error::InvalidCommand(format!("Unknown format \"{name}\""))),
+            None => Ok(Format::Text)
+        }
+    }
+
+    // who a correction or reopening is logged as, --by or else the login name
+    fn by(&self) -> Result<String, Error> {
+        match self.option("by") {
+            Some(by) => Ok(by.to_string()),
+            None => default_arbiter().ok_or_else(|| Error::InvalidCommand("Missing --by <name>, $USER is not set".to_string()))
+        }
+    }
+
+    // the first argument after the event, defaulting to what's left of the planned rounds
+    fn rounds_remaining(&self, tournament: &Tournament) -> Result<usize, Error> {
+        match self.positional.get(1) {
+            Some(rounds) => rounds.parse().map_err(|_| Error::InvalidCommand(format!("Invalid number of rounds \"{rounds}\""))),
+            None => tournament.rounds_remaining().ok_or_else(|| Error::InvalidCommand("Missing argument <rounds remaining>".to_string()))
+        }
+    }
+
+    // the --round option as an index, defaulting to the current round
+    fn round_idx(&self, tournament: &Tournament) -> Result<usize, Error> {
+
+        let round_number = match self.option("round") {
+            Some(round) => round.parse::<usize>()
+                .ok()
+                .filter(|&round_number| round_number > 0)
+                .ok_or_else(|| Error::InvalidCommand(format!("Invalid round \"{round}\"")))?,
+            None => tournament.rounds.len()
+        };
+
+        round_number.checked_sub(1).ok_or(Error::TournamentNotStarted)
+
+    }
+
+    // the section named by --section, which can be left out when there is only one
+    fn section(&self, event: &Event) -> Result<usize, Error> {
+        match self.option("section") {
+            Some(name) => event.section_index(name),
+            None if event.sections.len() == 1 => Ok(0),
+            None => Err(Error::InvalidCommand("The event has several sections, choose one with --section <name>".to_string()))
+        }
+    }
+
+    fn rating(&self, name: &str) -> Result<Option<u32>, Error> {
+        self.option(name)
+            .map(|rating| rating.parse().map_err(|_| Error::Parse(format!("Invalid elo \"{rating}\""))))
+            .transpose()
+    }
+
+    fn system(&self) -> Result<Option<PairingSystem>, Error> {
+        self.option("system")
+            .map(|name| PairingSystem::from_name(name).ok_or_else(|| Error::InvalidCommand(format!("Unknown pairing system \"{name}\""))))
+            .transpose()
+    }
+}
+
+/// Runs a non-interactive subcommand, returning the exit code,
+/// or `None` if the arguments don't name a subcommand.
+pub fn run_subcommand(args: &[String]) -> Option<i32> {
+
+    let subcommand = args.get(1)?;
+
+    if !SUBCOMMANDS.contains(&subcommand.as_str()) {
+        return None;
+    }
+
+    let result = Arguments::parse(&args[2..])
+        .and_then(|arguments| run(subcommand, &arguments));
+
+    Some(match result {
+        Ok(output) => {
+            print!("{output}");
+            0
+        }
+        Err(Error::InvalidCommand(message)) => {
+            eprintln!("Error: {message}.\n{USAGE}");
+            2
+        }
+        Err(err) => {
+            eprintln!("Error: {err}.");
+            1
+        }
+    })
+}
+
+fn run(subcommand: &str, arguments: &Arguments) -> Result<String, Error> {
+
+    if subcommand == "help" {
+        return Ok(format!("{USAGE}\n"));
+    }
+
+    let path = arguments.get(0, "event")?;
+
+    if subcommand == "new" {
+
+        if std::path::Path::new(path).exists() {
+            return Err(Error::File(path.to_string(), std::io::Error::new(std::io::ErrorKind::AlreadyExists, "already exists")));
+        }
+
+        let mut tournament = Tournament::new();
+
+        if let Some(players_path) = arguments.positional.get(1) {
+            tournament.add_players_from_file(players_path)?;
+        }
+
+        if let Some(system) = arguments.system()? {
+            tournament.system = system;
+        }
+
+        if let Some(name) = arguments.option("name") {
+            tournament.name = name.to_string();
+        }
+
+        if let Some(rounds) = arguments.option("rounds") {
+            tournament.total_rounds = Some(parse_total_rounds(rounds)?);
+        }
+
+        let num_players = tournament.players.len();
+        Event::from(tournament).save(path)?;
+
+        return Ok(format!("Created {path} with {num_players} players\n"));
+
+    }
+
+    let mut event = Event::load(path)?;
+
+    let output = match subcommand {
+        "section" => {
+
+            let name = arguments.get(1, "name")?.to_string();
+            let section_idx = event.add_section(name, arguments.rating("min-rating")?, arguments.rating("max-rating")?)?;
+
+            if let Some(system) = arguments.system()? {
+                event.sections[section_idx].tournament.system = system;
+            }
+
+            if let Some(first_board) = arguments.option("first-board") {
+                event.sections[section_idx].tournament.board_rules.first_board = Some(parse_first_board(first_board)?);
+            }
+
+            String::new()
+
+        }
+        "add" => {
+
+            let name = arguments.get(1, "name")?.to_string();
+
+            let rating = match arguments.positional.get(2) {
+                Some(rating) => Some(rating.parse().map_err(|_| Error::Parse(format!("Invalid elo \"{rating}\"")))?),
+                None => None
+            };
+
+            // without --section players go to the first section their rating allows
+            let section_idx = match arguments.option("section") {
+                Some(section_name) => event.section_index(section_name)?,
+                None => event.section_for_rating(rating)
+                    .ok_or_else(|| Error::Parse(format!("No section allows a rating of {}", rating.unwrap_or_default())))?
+            };
+
+            event.add_player(section_idx, name, rating)?;
+
+            let player = event.sections[section_idx].tournament.players.last_mut().unwrap();
+
+            if let Some(sex) = arguments.option("sex") {
+                player.sex = Some(Sex::from_name(sex).ok_or_else(|| Error::Parse(format!("Invalid sex \"{sex}\"")))?);
+            }
+
+            if let Some(year) = arguments.option("born") {
+                player.birth_year = Some(year.parse().map_err(|_| Error::Parse(format!("Invalid birth year \"{year}\"")))?);
+            }
+
+            if let Some(title) = arguments.option("title") {
+                player.title = Some(Title::from_name(title).ok_or_else(|| Error::Parse(format!("Invalid title \"{title}\"")))?);
+            }
+
+            String::new()
+
+        }
+        "pair" => {
+
+            let section_idx = arguments.section(&event)?;
+            let saved_system = event.sections[section_idx].tournament.system;
+
+            event.sections[section_idx].tournament.system = arguments.system()?.unwrap_or(saved_system);
+            let used_system = event.pair_next_round(section_idx);
+            event.sections[section_idx].tournament.system = saved_system;
+
+            let used_system = used_system?;
+            let tournament = &event.sections[section_idx].tournament;
+
+            if used_system != tournament.system {
+                eprintln!("Warning: Was not able to find {} pairings, settling with {} pairings.", tournament.system.name(), used_system.name());
+            }
+
+            if arguments.flag("alphabetical") {
+                alphabetical_output(tournament, arguments.format()?)
+            }
+            else {
+                pairings_output(tournament, arguments.format()?)
+            }
+        }
+        "swap" | "bye" | "colours" => {
+
+            let (section_idx, violations) = match subcommand {
+                "colours" => {
+
+                    let number_text = arguments.get(1, "board")?;
+
+                    let Ok(board_number) = number_text.parse::<u32>()
+                    else {
+                        return Err(Error::Parse(format!("Invalid board number \"{number_text}\"")));
+                    };
+
+                    let section_idx = match arguments.option("section") {
+                        Some(section_name) => event.section_index(section_name)?,
+                        None => event.find_board(board_number, 0)?
+                    };
+
+                    (section_idx, event.sections[section_idx].tournament.swap_colours(board_number)?)
+
+                }
+                "swap" => {
+
+                    let section_idx = arguments.section(&event)?;
+                    let tournament = &mut event.sections[section_idx].tournament;
+
+                    let player1 = tournament.find_player(arguments.get(1, "name")?)?;
+                    let player2 = tournament.find_player(arguments.get(2, "name")?)?;
+
+                    (section_idx, tournament.swap_players(player1, player2)?)
+
+                }
+                _ => {
+
+                    let section_idx = arguments.section(&event)?;
+                    let tournament = &mut event.sections[section_idx].tournament;
+                    let player = tournament.find_player(arguments.get(1, "name")?)?;
+
+                    (section_idx, tournament.move_bye(player)?)
+
+                }
+            };
+
+            let tournament = &event.sections[section_idx].tournament;
+            event.number_boards(section_idx, tournament.rounds.len() - 1);
+
+            let tournament = &event.sections[section_idx].tournament;
+
+            for violation in violations {
+                eprintln!("Warning: {}.", violation.as_string(&tournament.players));
+            }
+
+            pairings_output(tournament, Format::Text)
+
+        }
+        "result" if arguments.get(1, "board")?.contains(':') => {
+
+            if arguments.option("round").is_some() {
+                return Err(Error::InvalidCommand("Only one result of an earlier round can be corrected at a time".to_string()));
+            }
+
+            let results = parse_board_results(&arguments.positional[1..].join(" "))?;
+
+            match arguments.option("section") {
+                Some(section_name) => {
+                    let section_idx = event.section_index(section_name)?;
+                    event.sections[section_idx].tournament.report_results(&results)?;
+                }
+                None => event.report_results(&results, 0)?
+            }
+
+            pending_boards_message(&event.pending_boards()) + "\n"
+
+        }
+        "result" => {
+
+            let number_text = arguments.get(1, "board")?;
+
+            let Ok(board_number) = number_text.parse::<u32>()
+            else {
+                return Err(Error::Parse(format!("Invalid board number \"{number_text}\"")));
+            };
+
+            let result = GameResult::parse(arguments.get(2, "result")?)?;
+
+            if let Some(round) = arguments.option("round") {
+
+                let round_idx = round.parse::<usize>()
+                    .ok()
+                    .and_then(|round_number| round_number.checked_sub(1))
+                    .ok_or_else(|| Error::InvalidCommand(format!("Invalid round \"{round}\"")))?;
+
+                let section_idx = match arguments.option("section") {
+                    Some(section_name) => event.section_index(section_name)?,
+                    None => event.find_board_in_round(round_idx, board_number, 0)?
+                };
+
+                let by = arguments.by()?;
+                let tournament = &mut event.sections[section_idx].tournament;
+                let affected = tournament.correct_result(round_idx, board_number, result, &by)?;
+
+                for warning in text::correction_warnings(&affected, &tournament.players) {
+                    eprintln!("Warning: {warning}.");
+                }
+            }
+            else {
+                match arguments.option("section") {
+                    Some(section_name) => {
+                        let section_idx = event.section_index(section_name)?;
+                        event.sections[section_idx].tournament.report_result(board_number, result)?;
+                    }
+                    None => event.report_result(board_number, result)?
+                }
+            }
+
+            if arguments.option("round").is_some() {
+                String::new()
+            }
+            else {
+                pending_boards_message(&event.pending_boards()) + "\n"
+            }
+
+        }
+        "run" => {
+
+            let options = BatchOptions {
+                dry_run: arguments.flag("dry-run"),
+                keep_going: arguments.flag("keep-going")
+            };
+
+            // without --section a script starts in the first section and can switch with `section <name>`
+            let section_idx = match arguments.option("section") {
+                Some(name) => event.section_index(name)?,
+                None => 0
+            };
+
+            let mut session = Session {
+                section_idx,
+                event
+            };
+
+            let script_path = arguments.get(1, "script")?;
+            let script = std::fs::read_to_string(script_path).map_err(Error::file(script_path))?;
+            let failures = run_script(&mut session, &script, &options);
+
+            if !options.dry_run && (failures == 0 || options.keep_going) {
+                session.event.save(path)?;
+            }
+
+            if failures > 0 {
+                return Err(Error::Parse(format!("{failures} commands in the script failed")));
+            }
+
+            return Ok(String::new());
+
+        }
+        #[cfg(feature = "tui")]
+        "tui" => {
+            let section_idx = arguments.section(&event)?;
+            return crate::tui::run(&mut event, section_idx, Some(path)).map(|_| String::new());
+        }
+        #[cfg(not(feature = "tui"))]
+        "tui" => return Err(Error::InvalidCommand("Built without the terminal interface".to_string())),
+        #[cfg(feature = "server")]
+        "serve" => {
+
+            let section_idx = arguments.section(&event)?;
+            let port = arguments.option("port").unwrap_or("8080");
+
+            let Ok(port) = port.parse::<u16>()
+            else {
+                return Err(Error::InvalidCommand(format!("Invalid port \"{port}\"")));
+            };
+
+            // e.g. 0.0.0.0 to let tablets and display screens on the local network connect
+            let bind = arguments.option("bind").unwrap_or("127.0.0.1");
+
+            let Ok(ip) = bind.parse::<std::net::IpAddr>()
+            else {
+                return Err(Error::InvalidCommand(format!("Invalid address \"{bind}\"")));
+            };
+
+            let address = std::net::SocketAddr::new(ip, port);
+
+            // only a page from --origin may call the API from a browser
+            let access = swiss::server::Access::new(arguments.option("origin").map(str::to_string));
+
+            eprintln!("Serving {path} on http://{address}/api/");
+            eprintln!("Send \"Authorization: Bearer {}\" with every request.", access.token);
+
+            return swiss::server::serve(event, section_idx, &address.to_string(), Some(path.to_string()), access).map(|_| String::new());
+
+        }
+        #[cfg(not(feature = "server"))]
+        "serve" => return Err(Error::InvalidCommand("Built without the HTTP server".to_string())),
+        "html" if arguments.option("section").is_none() && event.sections.len() > 1 => match arguments.positional.get(1) {
+            Some(output_path) => {
+                std::fs::write(output_path, event.to_html()).map_err(Error::file(output_path))?;
+                return Ok(String::new());
+            }
+            None => return Ok(event.to_html())
+        },
+        _ => {
+
+            let section_idx = arguments.section(&event)?;
+            let tournament = &mut event.sections[section_idx].tournament;
+
+            match subcommand {
+                "remove" => {
+                    tournament.remove_player(arguments.get(1, "name")?)?;
+                    String::new()
+                }
+                "import" => {
+
+                    let pgn_path = arguments.get(1, "pgn file")?;
+                    let imports = tournament.import_pgn(&std::fs::read_to_string(pgn_path).map_err(Error::file(pgn_path))?)?;
+                    let mismatches = imports.iter().filter(|import| import.is_mismatch()).count();
+
+                    let invalid = imports.iter().filter(|import| import.is_invalid()).count();
+
+                    if mismatches > 0 {
+                        eprintln!("Warning: {mismatches} PGN results differ from the reported results.");
+                    }
+
+                    if invalid > 0 {
+                        eprintln!("Warning: {invalid} PGN games were skipped for an invalid result.");
+                    }
+
+                    imports.iter()
+                        .map(|import| import.as_string() + "\n")
+                        .collect()
+
+                }
+                "games" if arguments.flag("alphabetical") => return Ok(alphabetical_output(tournament, arguments.format()?)),
+                "games" => return Ok(pairings_output(tournament, arguments.format()?)),
+                "history" => {
+                    let player = tournament.find_player(arguments.get(1, "name")?)?;
+                    return Ok(history_output(tournament, player, arguments.format()?));
+                }
+                "standings" => {
+
+                    let format = arguments.format()?;
+                    let year = tournament.year();
+
+                    if let Some(name) = arguments.option("category") {
+                        return Ok(category_standings_output(tournament, parse_category(name)?, year, format));
+                    }
+
+                    let mut output = standings_output(tournament, format);
+
+                    if arguments.flag("categories") {
+                        for &category in tournament.categories.iter() {
+                            output += &category_standings_output(tournament, category, year, format);
+                        }
+                    }
+
+                    return Ok(output);
+
+                }
+                "categories" => {
+
+                    if arguments.positional.len() > 1 {
+                        tournament.categories = arguments.positional[1..].iter()
+                            .map(|name| parse_category(name))
+                            .collect::<Result<_, _>>()?;
+                    }
+
+                    tournament.categories.iter()
+                        .map(|category| category.name() + "\n")
+                        .collect()
+
+                }
+                "crosstable" => return Ok(crosstable_output(tournament, arguments.format()?)),
+                "prizes" => {
+                    let fund_path = arguments.get(1, "prize fund file")?;
+                    let fund = PrizeFund::parse(&std::fs::read_to_string(fund_path).map_err(Error::file(fund_path))?)?;
+                    return Ok(prize::payout_list(&fund.distribute(tournament, tournament.year()), &tournament.players));
+                }
+                "rounds" => {
+
+                    if let Some(rounds) = arguments.positional.get(1) {
+                        tournament.total_rounds = Some(parse_total_rounds(rounds)?);
+                    }
+
+                    tournament.rounds_text()
+
+                }
+                "corrections" => tournament.audit_log().into_iter()
+                    .map(|entry| entry + "\n")
+                    .collect(),
+                "boards" => {
+
+                    if let Some(name) = arguments.positional.get(1) {
+                        let player = tournament.find_player(name)?;
+                        set_board_rule(tournament, player, arguments.get(2, "board")?)?;
+                    }
+
+                    tournament.board_rules.as_string(&tournament.players)
+
+                }
+                "finish" if arguments.flag("reopen") => {
+                    tournament.reopen(&arguments.by()?)?;
+                    String::new()
+                }
+                "finish" => {
+                    tournament.finish()?;
+                    String::new()
+                }
+                "contention" => {
+
+                    let rounds_remaining = arguments.rounds_remaining(tournament)?;
+
+                    let places = match arguments.option("places") {
+                        Some(places) => places.parse()
+                            .ok()
+                            .filter(|&places| places > 0)
+                            .ok_or_else(|| Error::InvalidCommand(format!("Invalid number of places \"{places}\"")))?,
+                        None => 1
+                    };
+
+                    return Ok(contention_output(&tournament.contention(places, rounds_remaining), &tournament.players, arguments.format()?));
+
+                }
+                "forecast" => {
+
+                    let rounds_remaining = arguments.rounds_remaining(tournament)?;
+
+                    let num_simulations = match arguments.option("simulations") {
+                        Some(count) => count.parse()
+                            .ok()
+                            .filter(|&count| count > 0)
+                            .ok_or_else(|| Error::InvalidCommand(format!("Invalid number of simulations \"{count}\"")))?,
+                        None => DEFAULT_SIMULATIONS
+                    };
+
+                    let fund = match arguments.option("prizes") {
+                        Some(path) => Some(PrizeFund::parse(&std::fs::read_to_string(path).map_err(Error::file(path))?)?),
+                        None => None
+                    };
+
+                    let forecast = tournament.forecast(rounds_remaining, num_simulations, fund.as_ref(), tournament.year(), &mut rand::thread_rng())?;
+                    return Ok(forecast_output(&forecast, &tournament.players, arguments.format()?));
+
+                }
+                "print" => {
+
+                    let round_idx = arguments.round_idx(tournament)?;
+
+                    let Some(document) = tournament.print_html(round_idx)
+                    else {
+                        return Err(Error::Parse(format!("No round {}", round_idx + 1)));
+                    };
+
+                    match arguments.positional.get(1) {
+                        Some(output_path) => {
+                            std::fs::write(output_path, document).map_err(Error::file(output_path))?;
+                            return Ok(String::new());
+                        }
+                        None => return Ok(document)
+                    }
+                }
+                "pgn" => {
+
+                    let round_idx = arguments.round_idx(tournament)?;
+
+                    return tournament.round_pgn(round_idx)
+                        .ok_or_else(|| Error::Parse(format!("No round {}", round_idx + 1)));
+
+                }
+                "html" => match arguments.positional.get(1) {
+                    Some(output_path) => {
+                        std::fs::write(output_path, tournament.to_html()).map_err(Error::file(output_path))?;
+                        return Ok(String::new());
+                    }
+                    None => return Ok(tournament.to_html())
+                },
+                _ => unreachable!()
+            }
+        }
+    };
+
+    event.save(path)?;
+
+    Ok(output)
+
+}
+
+pub fn pairings_output(tournament: &Tournament, format: Format) -> String {
+
+    let Some(round_idx) = tournament.rounds.len().checked_sub(1)
+    else {
+        return match format {
+            Format::Text => "No rounds have been paired.\n".to_string(),
+            Format::Csv => tournament.round_csv(0),
+            Format::Json => "null\n".to_string(),
+            Format::Html => "<p>No rounds have been paired.</p>\n".to_string()
+        };
+    };
+
+    match format {
+        Format::Text => tournament.round_text(round_idx).unwrap(),
+        Format::Csv => tournament.round_csv(round_idx),
+        Format::Json => format!("{:#}\n", tournament.round_json(round_idx).unwrap()),
+        Format::Html => tournament.round_html(round_idx)
+    }
+}
+
+pub fn alphabetical_output(tournament: &Tournament, format: Format) -> String {
+
+    let Some(round_idx) = tournament.rounds.len().checked_sub(1)
+    else {
+        return pairings_output(tournament, format);
+    };
+
+    match format {
+        Format::Text => tournament.alphabetical_text(round_idx).unwrap(),
+        Format::Csv => tournament.alphabetical_csv(round_idx).unwrap(),
+        Format::Json => format!("{:#}\n", tournament.alphabetical_json(round_idx).unwrap()),
+        Format::Html => tournament.alphabetical_html(round_idx).unwrap()
+    }
+}
+
+pub fn history_output(tournament: &Tournament, player: PlayerID, format: Format) -> String {
+    match format {
+        Format::Text => tournament.history_text(player),
+        Format::Csv => tournament.history_csv(player),
+        Format::Json => format!("{:#}\n", tournament.history_json(player)),
+        Format::Html => tournament.history_html(player)
+    }
+}
+
+pub fn standings_output(tournament: &Tournament, format: Format) -> String {
+    match format {
+        Format::Text => tournament.standings_text(),
+        Format::Csv => tournament.standings_csv(),
+        Format::Json => format!("{:#}\n", tournament.standings_json()),
+        Format::Html => tournament.standings_html()
+    }
+}
+
+pub fn parse_total_rounds(text: &str) -> Result<usize, Error> {
+    text.parse()
+        .ok()
+        .filter(|&rounds| rounds > 0)
+        .ok_or_else(|| Error::InvalidCommand(format!("Invalid number of rounds \"{text}\"")))
+}
+
+/// A section's first board number, from 1.
+pub fn parse_first_board(text: &str) -> Result<u32, Error> {
+    text.trim().parse()
+        .ok()
+        .filter(|&board| board > 0)
+        .ok_or_else(|| Error::Parse(format!("Invalid board number \"{}\"", text.trim())))
+}
+
+/// Who corrections are logged as when nobody is named, the login name if there is one.
+pub fn default_arbiter() -> Option<String> {
+    std::env::var("USER").ok().filter(|user| !user.trim().is_empty())
+}
+
+/// Applies a board number, `top` or `clear` to the player, taking effect from the next round.
+pub fn set_board_rule(tournament: &mut Tournament, player: PlayerID, rule: &str) -> Result<(), Error> {
+
+    match rule.trim().to_lowercase().as_str() {
+        "top" => tournament.board_rules.pin(player),
+        "clear" | "none" => tournament.board_rules.clear(player),
+        board => {
+
+            let Some(board) = board.parse().ok().filter(|&board| board > 0)
+            else {
+                return Err(Error::InvalidCommand(format!("Invalid board \"{rule}\"")));
+            };
+
+            tournament.board_rules.fix(player, board);
+
+        }
+    }
+
+    Ok(())
+
+}
+
+pub fn parse_category(name: &str) -> Result<Category, Error> {
+    Category::from_name(name).ok_or_else(|| Error::InvalidCommand(format!("Unknown category \"{name}\"")))
+}
+
+pub fn category_standings_output(tournament: &Tournament, category: Category, year: u32, format: Format) -> String {
+    match format {
+        Format::Text => tournament.category_standings_text(category, year),
+        Format::Csv => tournament.category_standings_csv(category, year),
+        Format::Json => format!("{:#}\n", tournament.category_standings_json(category, year)),
+        Format::Html => tournament.category_standings_html(category, year)
+    }
+}
+
+pub fn contention_output(table: &ContentionTable, players: &[Player], format: Format) -> String {
+    match format {
+        Format::Text => table.as_string(players),
+        Format::Csv => table.as_csv(players),
+        Format::Json => format!("{:#}\n", table.as_json(players)),
+        Format::Html => table.as_html(players)
+    }
+}
+
+pub fn forecast_output(forecast: &Forecast, players: &[Player], format: Format) -> String {
+    match format {
+        Format::Text => forecast.as_string(players),
+        Format::Csv => forecast.as_csv(players),
+        Format::Json => format!("{:#}\n", forecast.as_json(players)),
+        Format::Html => forecast.as_html(players)
+    }
+}
+
+pub fn crosstable_output(tournament: &Tournament, format: Format) -> String {
+    match format {
+        Format::Text => tournament.crosstable_text(),
+        Format::Csv => tournament.crosstable().as_csv(&tournament.players),
+        Format::Json => format!("{:#}\n", tournament.crosstable_json()),
+        Format::Html => tournament.crosstable().as_html(&tournament.players)
+    }
+}
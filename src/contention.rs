@@ -0,0 +1,242 @@
+use crate::*;
+
+use csv::{csv_field, number_field};
+use html::{escape, withdrawn_class};
+
+use serde_json::{json, Value};
+
+const OUTCOMES: [f32; 3] = [0.0, 0.5, 1.0];
+
+/// Whether a player can still finish within a number of places, judged on score alone.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Contention {
+    /// Finishes within the places whatever the remaining results, even losing every tiebreak.
+    Clinched,
+    Contending,
+    /// Can't reach the places even winning every remaining game.
+    Eliminated
+}
+
+impl Contention {
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Clinched => "clinched",
+            Self::Contending => "in contention",
+            Self::Eliminated => "eliminated"
+        }
+    }
+}
+
+pub struct ContentionRow {
+    pub player: PlayerID,
+    pub score: f32,
+    /// With every pending and remaining game won.
+    pub max_score: f32,
+    pub contention: Contention
+}
+
+/// Who can still finish within the top `places`, in standings order.
+pub struct ContentionTable {
+    pub places: usize,
+    pub rounds_remaining: usize,
+    pub rows: Vec<ContentionRow>
+}
+
+impl Tournament {
+
+    /// Decides for every player whether they have clinched, are contending for or are eliminated from the top `places`
+    /// with `rounds_remaining` rounds still to be paired after the current one.
+    /// Pending games of the current round are taken into account, later pairings aren't known so every other player
+    /// is assumed to be able to win or lose all of their remaining games. Players tied on score may finish either way
+    /// on tiebreaks, so a player is only clinched or eliminated when that holds whatever the tiebreaks.
+    pub fn contention(&self, places: usize, rounds_remaining: usize) -> ContentionTable {
+
+        let scores = self.get_player_scores();
+
+        let mut pending_opponents = vec![None; self.players.len()];
+
+        if let Some(round) = self.current_round() {
+            for game in round.games.iter().filter(|game| game.result == GameResult::Pending) {
+                pending_opponents[game.white_player] = Some(game.black_player);
+                pending_opponents[game.black_player] = Some(game.white_player);
+            }
+        }
+
+        // withdrawn players won't be paired again
+        let future_points = |player: PlayerID| if self.players[player].active {
+            rounds_remaining as f32
+        }
+        else {
+            0.0
+        };
+
+        let max_score = |player: PlayerID| {
+            scores[player] + if pending_opponents[player].is_some() { 1.0 } else { 0.0 } + future_points(player)
+        };
+
+        // counts the rivals of `player` that `reaches` holds for given their points from the current round,
+        // taking the best or worst case of each pending game between two rivals
+        let count_rivals = |player: PlayerID, points_against_player: f32, reaches: &dyn Fn(PlayerID, f32) -> bool, best_case: bool| {
+
+            let mut count = 0;
+
+            for rival in (0..self.players.len()).filter(|&rival| rival != player) {
+                match pending_opponents[rival] {
+                    Some(opponent) if opponent == player => count += reaches(rival, points_against_player) as usize,
+                    Some(opponent) => {
+
+                        // each pending game between two rivals is counted once
+                        if opponent < rival {
+                            continue;
+                        }
+
+                        let counts = OUTCOMES.iter().map(|&points| reaches(rival, points) as usize + reaches(opponent, 1.0 - points) as usize);
+
+                        count += if best_case { counts.max() } else { counts.min() }.unwrap_or(0);
+
+                    }
+                    None => count += reaches(rival, 0.0) as usize
+                }
+            }
+
+            count
+
+        };
+
+        let rows = self.standings().into_iter()
+            .map(|standing| {
+
+                let player = standing.player;
+
+                // the most rivals that could finish level or above if the player loses everything
+                let worst_score = scores[player];
+                let could_pass = |rival: PlayerID, points: f32| scores[rival] + points + future_points(rival) >= worst_score;
+                let threats = count_rivals(player, 1.0, &could_pass, true);
+
+                // the fewest rivals sure to finish above if the player wins everything
+                let best_score = max_score(player);
+                let surely_above = |rival: PlayerID, points: f32| scores[rival] + points > best_score;
+                let above = count_rivals(player, 0.0, &surely_above, false);
+
+                let contention = if threats < places {
+                    Contention::Clinched
+                }
+                else if above >= places {
+                    Contention::Eliminated
+                }
+                else {
+                    Contention::Contending
+                };
+
+                ContentionRow {
+                    player,
+                    score: standing.score,
+                    max_score: best_score,
+                    contention
+                }
+            })
+            .collect();
+
+        ContentionTable {
+            places,
+            rounds_remaining,
+            rows
+        }
+    }
+}
+
+impl ContentionTable {
+
+    pub fn as_string(&self, players: &[Player]) -> String {
+
+        let name_width = self.rows.iter()
+            .map(|row| players[row.player].label().chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+
+        let mut output = format!("{:<name_width$}  Score  Max score  Top {}\n", "Name", self.places);
+
+        for row in self.rows.iter() {
+            output += &format!(
+                "{:<name_width$}  {:>5.1}  {:>9.1}  {}\n",
+                players[row.player].label(),
+                row.score,
+                row.max_score,
+                row.contention.name()
+            );
+        }
+
+        output
+
+    }
+
+    pub fn as_csv(&self, players: &[Player]) -> String {
+
+        let mut output = "pairing_number,name,score,max_score,status\n".to_string();
+
+        for row in self.rows.iter() {
+
+            let player = &players[row.player];
+
+            output += &format!(
+                "{},{},{},{},{}\n",
+                number_field(player),
+                csv_field(&player.name),
+                row.score,
+                row.max_score,
+                row.contention.name()
+            );
+
+        }
+
+        output
+
+    }
+
+    pub fn as_json(&self, players: &[Player]) -> Value {
+
+        let rows: Vec<_> = self.rows.iter()
+            .map(|row| json!({
+                "id": row.player,
+                "pairing_number": players[row.player].pairing_number,
+                "name": players[row.player].name,
+                "score": row.score,
+                "max_score": row.max_score,
+                "status": row.contention.name()
+            }))
+            .collect();
+
+        json!({
+            "places": self.places,
+            "rounds_remaining": self.rounds_remaining,
+            "players": rows
+        })
+    }
+
+    /// A `<table>` of each player's score, best possible score and status for the places.
+    pub fn as_html(&self, players: &[Player]) -> String {
+
+        let mut html = format!("<table>\n<tr><th>Name</th><th>Score</th><th>Max score</th><th>Top {}</th></tr>\n", self.places);
+
+        for row in self.rows.iter() {
+
+            let player = &players[row.player];
+
+            html += &format!(
+                "<tr{}><td>{}</td><td class=\"number\">{:.1}</td><td class=\"number\">{:.1}</td><td>{}</td></tr>\n",
+                withdrawn_class(player),
+                escape(&player.label()),
+                row.score,
+                row.max_score,
+                row.contention.name()
+            );
+        }
+
+        html += "</table>\n";
+
+        html
+
+    }
+}
@@ -0,0 +1,253 @@
+use crate::*;
+
+use serde::{Deserialize, Serialize};
+
+/// An audit record of a result changed with [`Tournament::correct_result`].
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Correction {
+    /// Starting from 1.
+    pub round: usize,
+    pub board: u32,
+    pub white_player: PlayerID,
+    pub black_player: PlayerID,
+    pub old_result: GameResult,
+    pub new_result: GameResult,
+    /// Whoever made the change, e.g. the arbiter's name.
+    pub by: String,
+    /// Seconds since the Unix epoch.
+    pub time: u64
+}
+
+impl Correction {
+
+    /// E.g. `2024-05-04 14:31 UTC, round 2 board 3: Ann - Bob 1-0 -> ½-½ (by Chief Arbiter)`.
+    pub fn as_string(&self, players: &[Player]) -> String {
+
+        let mut output = format!(
+            "{}, round {} board {}: {} - {} {} -> {}",
+            timestamp(self.time),
+            self.round,
+            self.board,
+            players[self.white_player].label(),
+            players[self.black_player].label(),
+            self.old_result.as_notation(),
+            self.new_result.as_notation()
+        );
+
+        if !self.by.is_empty() {
+            output += &format!(" (by {})", self.by);
+        }
+
+        output
+
+    }
+}
+
+/// An audit record of a finished tournament unlocked again with [`Tournament::reopen`].
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Reopening {
+    pub by: String,
+    /// Seconds since the Unix epoch.
+    pub time: u64
+}
+
+impl Reopening {
+
+    /// E.g. `2024-05-04 14:31 UTC, reopened by Chief Arbiter`.
+    pub fn as_string(&self) -> String {
+        format!("{}, reopened by {}", timestamp(self.time), self.by)
+    }
+}
+
+impl Tournament {
+
+    /// Unlocks a finished tournament so results can be changed and rounds paired again, keeping an audit record.
+    /// Does nothing if the tournament isn't finished.
+    pub fn reopen(&mut self, by: &str) -> Result<(), Error> {
+
+        if !self.finished {
+            return Ok(());
+        }
+
+        if by.trim().is_empty() {
+            return Err(Error::Parse("Missing \"by\" for reopening".to_string()));
+        }
+
+        self.finished = false;
+
+        self.reopenings.push(Reopening {
+            by: by.trim().to_string(),
+            time: category::unix_time()
+        });
+
+        Ok(())
+
+    }
+
+    /// Every correction and reopening, oldest first.
+    pub fn audit_log(&self) -> Vec<String> {
+
+        let mut entries: Vec<_> = self.corrections.iter()
+            .map(|correction| (correction.time, correction.as_string(&self.players)))
+            .chain(self.reopenings.iter().map(|reopening| (reopening.time, reopening.as_string())))
+            .collect();
+
+        entries.sort_by_key(|(time, _)| *time);
+
+        entries.into_iter()
+            .map(|(_, entry)| entry)
+            .collect()
+
+    }
+
+    /// Changes the result of a game in any round (0-indexed), keeping an audit record.
+    /// Returns, for each later round whose pairings the engine would now make differently,
+    /// the players who might have had another opponent or bye.
+    pub fn correct_result(&mut self, round_idx: usize, board_number: u32, result: GameResult, by: &str) -> Result<Vec<(usize, Vec<PlayerID>)>, Error> {
+
+        if self.finished {
+            return Err(Error::TournamentFinished);
+        }
+
+        if by.trim().is_empty() {
+            return Err(Error::Parse("Missing \"by\" for correction".to_string()));
+        }
+
+        let round = self.rounds.get_mut(round_idx)
+            .ok_or_else(|| Error::Parse(format!("No round {}", round_idx + 1)))?;
+
+        let game = round.games.iter_mut()
+            .find(|game| game.board_number == board_number)
+            .ok_or(Error::UnknownBoard(board_number))?;
+
+        let old_result = game.result;
+
+        if old_result == result {
+            return Ok(Vec::new());
+        }
+
+        game.result = result;
+
+        self.corrections.push(Correction {
+            round: round_idx + 1,
+            board: board_number,
+            white_player: game.white_player,
+            black_player: game.black_player,
+            old_result,
+            new_result: result,
+            by: by.trim().to_string(),
+            time: category::unix_time()
+        });
+
+        let mut original = self.clone();
+        original.rounds[round_idx].games.iter_mut()
+            .filter(|game| game.board_number == board_number)
+            .for_each(|game| game.result = old_result);
+
+        let mut affected = Vec::new();
+
+        for later_idx in round_idx + 1..self.rounds.len() {
+
+            let before = |tournament: &Tournament| Tournament {
+                rounds: tournament.rounds[..later_idx].to_vec(),
+                ..tournament.clone()
+            };
+
+            let (Ok((original_round, _)), Ok((corrected_round, _))) = (
+                self.system.generate_with_fallback(&before(&original)),
+                self.system.generate_with_fallback(&before(self))
+            )
+            else {
+                continue;
+            };
+
+            if original_round.same_pairings(&corrected_round) {
+                continue;
+            }
+
+            let players: Vec<_> = (0..self.players.len())
+                .filter(|&player| partner(&original_round, player) != partner(&corrected_round, player))
+                .collect();
+
+            affected.push((later_idx, players));
+
+        }
+
+        Ok(affected)
+
+    }
+}
+
+// the opponent, or the player themselves for a bye
+fn partner(round: &Round, player: PlayerID) -> Option<PlayerID> {
+
+    if round.bye_player == Some(player) {
+        return Some(player);
+    }
+
+    round.games.iter().find_map(|game| {
+        if game.white_player == player {
+            Some(game.black_player)
+        }
+        else if game.black_player == player {
+            Some(game.white_player)
+        }
+        else {
+            None
+        }
+    })
+}
+
+// e.g. `2024-05-04 14:31 UTC`
+fn timestamp(seconds: u64) -> String {
+
+    let (year, month, day) = category::utc_date(seconds);
+
+    format!("{year}-{month:02}-{day:02} {:02}:{:02} UTC", seconds / 3600 % 24, seconds / 60 % 60)
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+    use crate::testing::played_tournament;
+
+    // round 1 played as 1-3 1-0, 4-2 0-1, round 2 as 2-1 ½-½, 3-4 1-0 and round 3 paired as 4-1, 2-3
+    fn tournament() -> Tournament {
+        played_tournament(4, &[
+            (&[(1, 3, GameResult::Win), (4, 2, GameResult::Loss)], None),
+            (&[(2, 1, GameResult::Draw), (3, 4, GameResult::Win)], None),
+            (&[(4, 1, GameResult::Pending), (2, 3, GameResult::Pending)], None)
+        ])
+    }
+
+    #[test]
+    fn correcting_an_early_result_reports_the_rounds_it_would_have_changed() {
+
+        let mut tournament = tournament();
+
+        // with 3 beating 1 the leaders after round 1 are 2 and 3, so round 2 would have been 2-3 and 1-4;
+        // round 3 comes out the same because 1 and 2 have already met
+        let affected = tournament.correct_result(0, 1, GameResult::Loss, "Chief Arbiter").unwrap();
+        assert_eq!(affected, [(1, vec![0, 1, 2, 3])]);
+
+        assert!(tournament.rounds[0].games[0].result == GameResult::Loss);
+        assert_eq!(tournament.corrections.len(), 1);
+
+        let correction = &tournament.corrections[0];
+        assert_eq!((correction.round, correction.board), (1, 1));
+        assert_eq!((correction.white_player, correction.black_player), (0, 2));
+        assert!(correction.old_result == GameResult::Win && correction.new_result == GameResult::Loss);
+        assert_eq!(correction.by, "Chief Arbiter");
+
+        // nobody to log it as
+        assert!(matches!(tournament.correct_result(1, 1, GameResult::Loss, " "), Err(Error::Parse(_))));
+        assert!(tournament.rounds[1].games[0].result == GameResult::Draw);
+
+        // the same result again changes nothing and isn't recorded
+        assert!(tournament.correct_result(0, 1, GameResult::Loss, "Chief Arbiter").unwrap().is_empty());
+        assert_eq!(tournament.corrections.len(), 1);
+
+    }
+}
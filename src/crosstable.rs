@@ -0,0 +1,202 @@
+use crate::*;
+
+use csv::{csv_field, number_field};
+use html::{escape, withdrawn_class};
+
+/// What a player did in one round of the crosstable.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CrosstableEntry {
+    Game {
+        /// The opponent's rank in the crosstable.
+        opponent_rank: usize,
+        white: bool,
+        /// From this player's point of view.
+        result: GameResult
+    },
+    Bye,
+    /// Not paired, e.g. after withdrawing or joining late.
+    Absent
+}
+
+impl CrosstableEntry {
+
+    /// Wall chart notation such as `12w1`, `5b½`, `7b+` for a win by forfeit, `+` for a bye or `-` when absent.
+    pub fn as_string(&self) -> String {
+        match self {
+            Self::Game { opponent_rank, white, result } => {
+
+                let colour = if *white { 'w' } else { 'b' };
+
+                let score = match result {
+                    GameResult::Win => "1",
+                    GameResult::Draw => "½",
+                    GameResult::Loss => "0",
+                    GameResult::ForfeitWin => "+",
+                    GameResult::ForfeitLoss | GameResult::DoubleForfeit => "-",
+                    GameResult::Pending => ""
+                };
+
+                format!("{opponent_rank}{colour}{score}")
+
+            }
+            Self::Bye => "+".to_string(),
+            Self::Absent => "-".to_string()
+        }
+    }
+}
+
+pub struct CrosstableRow {
+    /// Position in the crosstable, unlike [`Standing::place`] this is never shared.
+    pub rank: usize,
+    pub player: PlayerID,
+    /// One per round.
+    pub entries: Vec<CrosstableEntry>,
+    pub score: f32,
+    pub sonneborn_berger: f32
+}
+
+/// A wall chart with one row per player in standings order, referring to opponents by rank.
+pub struct Crosstable {
+    pub rows: Vec<CrosstableRow>,
+    pub num_rounds: usize
+}
+
+impl Tournament {
+
+    pub fn crosstable(&self) -> Crosstable {
+
+        let standings = self.standings();
+        let mut ranks = vec![0; self.players.len()];
+
+        for (idx, standing) in standings.iter().enumerate() {
+            ranks[standing.player] = idx + 1;
+        }
+
+        let rows = standings.iter()
+            .enumerate()
+            .map(|(idx, standing)| {
+
+                let player = standing.player;
+
+                let entries = self.rounds.iter()
+                    .map(|round| match round.seat(player) {
+                        Some(Seat::Board { opponent, white, result, .. }) => CrosstableEntry::Game {
+                            opponent_rank: ranks[opponent],
+                            white,
+                            result
+                        },
+                        Some(Seat::Bye) => CrosstableEntry::Bye,
+                        None => CrosstableEntry::Absent
+                    })
+                    .collect();
+
+                CrosstableRow {
+                    rank: idx + 1,
+                    player,
+                    entries,
+                    score: standing.score,
+                    sonneborn_berger: standing.sonneborn_berger
+                }
+            })
+            .collect();
+
+        Crosstable {
+            rows,
+            num_rounds: self.rounds.len()
+        }
+    }
+}
+
+impl Crosstable {
+
+    pub fn as_string(&self, players: &[Player]) -> String {
+
+        let name_width = self.rows.iter()
+            .map(|row| players[row.player].label().chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+
+        let mut output = format!("{:>3}  {:<name_width$}", "##", "Name");
+
+        for round_idx in 0..self.num_rounds {
+            output += &format!(" {:>5}", round_idx + 1);
+        }
+
+        output += "  Score  SB Score\n";
+
+        for row in self.rows.iter() {
+
+            output += &format!("{:>3}  {:<name_width$}", row.rank, players[row.player].label());
+
+            for entry in row.entries.iter() {
+                output += &format!(" {:>5}", entry.as_string());
+            }
+
+            output += &format!("  {:>5.1}  {:>8.2}\n", row.score, row.sonneborn_berger);
+
+        }
+
+        output
+
+    }
+
+    pub fn as_csv(&self, players: &[Player]) -> String {
+
+        let mut output = "rank,pairing_number,name".to_string();
+
+        for round_idx in 0..self.num_rounds {
+            output += &format!(",round_{}", round_idx + 1);
+        }
+
+        output += ",score,sonneborn_berger\n";
+
+        for row in self.rows.iter() {
+
+            let player = &players[row.player];
+
+            output += &format!("{},{},{}", row.rank, number_field(player), csv_field(&player.name));
+
+            for entry in row.entries.iter() {
+                output += &format!(",{}", entry.as_string());
+            }
+
+            output += &format!(",{},{}\n", row.score, row.sonneborn_berger);
+
+        }
+
+        output
+
+    }
+
+    /// A `<table>` with a column per round, each cell as in the text crosstable.
+    pub fn as_html(&self, players: &[Player]) -> String {
+
+        let mut html = "<table>\n<tr><th>#</th><th>Name</th>".to_string();
+
+        for round_idx in 0..self.num_rounds {
+            html += &format!("<th>{}</th>", round_idx + 1);
+        }
+
+        html += "<th>Score</th><th>SB</th></tr>\n";
+
+        for row in self.rows.iter() {
+
+            let player = &players[row.player];
+
+            html += &format!("<tr{}><td class=\"number\">{}</td><td>{}</td>", withdrawn_class(player), row.rank, escape(&player.label()));
+
+            for entry in row.entries.iter() {
+                html += &format!("<td class=\"result\">{}</td>", entry.as_string());
+            }
+
+            html += &format!("<td class=\"number\">{:.1}</td><td class=\"number\">{:.2}</td></tr>\n", row.score, row.sonneborn_berger);
+
+        }
+
+        html += "</table>\n";
+
+        html
+
+    }
+}
@@ -0,0 +1,155 @@
+use crate::*;
+
+use category::Category;
+
+/// CSV views of a tournament with a header row, players given by pairing number and name.
+impl Tournament {
+
+    /// Just the header if the round doesn't exist.
+    pub fn round_csv(&self, round_idx: usize) -> String {
+
+        let mut output = "board,white_number,white,black_number,black,result\n".to_string();
+
+        let Some(round) = self.rounds.get(round_idx)
+        else {
+            return output;
+        };
+
+        let player = |id: PlayerID| &self.players[id];
+
+        for game in round.games.iter() {
+
+            let (white, black) = (player(game.white_player), player(game.black_player));
+
+            output += &format!(
+                "{},{},{},{},{},{}\n",
+                game.board_number,
+                number_field(white),
+                csv_field(&white.name),
+                number_field(black),
+                csv_field(&black.name),
+                game.result.as_notation()
+            );
+        }
+
+        if let Some(bye_player) = round.bye_player {
+            output += &format!("bye,{},{},,,\n", number_field(player(bye_player)), csv_field(&player(bye_player).name));
+        }
+
+        output
+
+    }
+
+    pub fn alphabetical_csv(&self, round_idx: usize) -> Option<String> {
+
+        let name = |id: PlayerID| self.players[id].name.as_str();
+        let number = |id: PlayerID| number_field(&self.players[id]);
+
+        let mut output = "pairing_number,name,board,colour,opponent_number,opponent\n".to_string();
+
+        for (player, seat) in self.alphabetical_pairings(round_idx)? {
+            output += &match seat {
+                Seat::Board { board, white, opponent, .. } => {
+                    format!("{},{},{board},{},{},{}\n", number(player), csv_field(name(player)), if white { "white" } else { "black" }, number(opponent), csv_field(name(opponent)))
+                }
+                Seat::Bye => format!("{},{},,,,bye\n", number(player), csv_field(name(player)))
+            };
+        }
+
+        Some(output)
+
+    }
+
+    pub fn history_csv(&self, player: PlayerID) -> String {
+
+        let mut output = "round,board,colour,result,opponent_number,opponent\n".to_string();
+
+        for (round_idx, seat) in self.history(player).into_iter().enumerate() {
+            output += &match seat {
+                Some(Seat::Board { board, white, opponent, result }) => format!(
+                    "{},{board},{},{},{},{}\n",
+                    round_idx + 1,
+                    if white { "white" } else { "black" },
+                    result.as_letter().to_string().trim(),
+                    number_field(&self.players[opponent]),
+                    csv_field(&self.players[opponent].name)
+                ),
+                Some(Seat::Bye) => format!("{},,,W,,bye\n", round_idx + 1),
+                None => format!("{},,,,,\n", round_idx + 1)
+            };
+        }
+
+        output
+
+    }
+
+    pub fn standings_csv(&self) -> String {
+
+        let mut output = "place,pairing_number,name,rating,score,sonneborn_berger,wins,draws,losses,byes,active\n".to_string();
+
+        for standing in self.standings() {
+
+            let player = &self.players[standing.player];
+
+            output += &format!(
+                "{},{},{},{},{},{},{},{},{},{},{}\n",
+                standing.place,
+                number_field(player),
+                csv_field(&player.name),
+                player.rating.map(|rating| rating.to_string()).unwrap_or_default(),
+                standing.score,
+                standing.sonneborn_berger,
+                standing.wins,
+                standing.draws,
+                standing.losses,
+                standing.byes,
+                player.active
+            );
+        }
+
+        output
+
+    }
+
+    pub fn category_standings_csv(&self, category: Category, year: u32) -> String {
+
+        let mut output = "category,place,overall_place,pairing_number,name,rating,score,sonneborn_berger,active\n".to_string();
+
+        for category_standing in self.category_standings(category, year) {
+
+            let standing = &category_standing.standing;
+            let player = &self.players[standing.player];
+
+            output += &format!(
+                "{},{},{},{},{},{},{},{},{}\n",
+                category.name(),
+                category_standing.place,
+                standing.place,
+                number_field(player),
+                csv_field(&player.name),
+                player.rating.map(|rating| rating.to_string()).unwrap_or_default(),
+                standing.score,
+                standing.sonneborn_berger,
+                player.active
+            );
+        }
+
+        output
+
+    }
+}
+
+/// Quotes the text if it has a comma, quote or line break.
+pub(crate) fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    }
+    else {
+        text.to_string()
+    }
+}
+
+/// The pairing number, blank until the tournament starts.
+pub(crate) fn number_field(player: &Player) -> String {
+    player.pairing_number.map(|number| number.to_string()).unwrap_or_default()
+}
@@ -0,0 +1,135 @@
+use crate::*;
+
+use checker::Violation;
+
+// where a player sits in a round
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Spot {
+    White(usize),
+    Black(usize),
+    Bye
+}
+
+/// Changes to the pairings of the current round before it is played.
+/// Each edit renumbers the boards and returns the pairing rules the round now breaks.
+impl Tournament {
+
+    /// Swaps two players between boards, or moves the bye if one of them has it.
+    pub fn swap_players(&mut self, player1: PlayerID, player2: PlayerID) -> Result<Vec<Violation>, Error> {
+
+        let spot1 = self.editable_spot(player1)?;
+        let spot2 = self.editable_spot(player2)?;
+
+        let same_board = match (spot1, spot2) {
+            (Spot::White(game1) | Spot::Black(game1), Spot::White(game2) | Spot::Black(game2)) => game1 == game2,
+            _ => spot1 == spot2
+        };
+
+        if same_board {
+            return Err(Error::Parse(format!(
+                "{} and {} are paired together, swap colours instead",
+                self.players[player1].label(),
+                self.players[player2].label()
+            )));
+        }
+
+        let round = self.rounds.last_mut().unwrap();
+
+        for (spot, player) in [(spot1, player2), (spot2, player1)] {
+            match spot {
+                Spot::White(game_idx) => round.games[game_idx].white_player = player,
+                Spot::Black(game_idx) => round.games[game_idx].black_player = player,
+                Spot::Bye => round.bye_player = Some(player)
+            }
+        }
+
+        Ok(self.renumber_and_check())
+
+    }
+
+    /// Gives the bye to another player, who takes the place of the player who had it.
+    pub fn move_bye(&mut self, player: PlayerID) -> Result<Vec<Violation>, Error> {
+
+        let round = self.current_round().ok_or(Error::TournamentNotStarted)?;
+
+        let Some(bye_player) = round.bye_player
+        else {
+            return Err(Error::Parse("There is no bye this round".to_string()));
+        };
+
+        if bye_player == player {
+            return Err(Error::Parse(format!("{} already has the bye", self.players[player].label())));
+        }
+
+        self.swap_players(bye_player, player)
+
+    }
+
+    pub fn swap_colours(&mut self, board_number: u32) -> Result<Vec<Violation>, Error> {
+
+        if self.finished {
+            return Err(Error::TournamentFinished);
+        }
+
+        let round = self.rounds.last_mut().ok_or(Error::TournamentNotStarted)?;
+
+        let game = round.games.iter_mut()
+            .find(|game| game.board_number == board_number)
+            .ok_or(Error::UnknownBoard(board_number))?;
+
+        if game.result != GameResult::Pending {
+            return Err(Error::Parse(format!("Board {board_number} already has a result")));
+        }
+
+        std::mem::swap(&mut game.white_player, &mut game.black_player);
+
+        Ok(self.renumber_and_check())
+
+    }
+
+    /// The rules the current round breaks, as if it were about to be paired.
+    pub fn check_current_round(&mut self) -> Vec<Violation> {
+
+        let Some(round) = self.rounds.pop()
+        else {
+            return Vec::new();
+        };
+
+        let violations = round.check(self, None);
+        self.rounds.push(round);
+
+        violations
+
+    }
+
+    fn editable_spot(&self, player: PlayerID) -> Result<Spot, Error> {
+
+        if self.finished {
+            return Err(Error::TournamentFinished);
+        }
+
+        let round = self.current_round().ok_or(Error::TournamentNotStarted)?;
+
+        if round.bye_player == Some(player) {
+            return Ok(Spot::Bye);
+        }
+
+        let (game_idx, game) = round.games.iter()
+            .enumerate()
+            .find(|(_, game)| game.white_player == player || game.black_player == player)
+            .ok_or_else(|| Error::Parse(format!("{} is not paired this round", self.players[player].label())))?;
+
+        if game.result != GameResult::Pending {
+            return Err(Error::Parse(format!("Board {} already has a result", game.board_number)));
+        }
+
+        Ok(if game.white_player == player { Spot::White(game_idx) } else { Spot::Black(game_idx) })
+
+    }
+
+    // in an event, boards fixed for other sections are only kept free once the event renumbers the round
+    fn renumber_and_check(&mut self) -> Vec<Violation> {
+        self.number_boards(self.rounds.len() - 1, self.board_rules.first_board(), &[]);
+        self.check_current_round()
+    }
+}
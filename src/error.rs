@@ -1,4 +1,68 @@
-pub fn error(message: impl Into<String>) -> ! {
-    println!("Error: {}.", message.into());
-    std::process::exit(1)
-}
\ No newline at end of file
+use crate::algorithms::PairingSystem;
+
+use std::fmt::Display;
+
+/// Everything that can go wrong in the library; the CLI decides how to show these.
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    /// A file that couldn't be read or written.
+    File(String, std::io::Error),
+    Parse(String),
+    PairingImpossible(PairingSystem),
+    InvalidCommand(String),
+    UnknownPlayer(String),
+    /// The search and every player it could mean.
+    AmbiguousPlayer(String, Vec<String>),
+    UnknownBoard(u32),
+    UnknownSection(String),
+    TournamentNotStarted,
+    TournamentFinished,
+    AllRoundsPaired(usize),
+    GamesPending(Vec<u32>)
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{err}"),
+            Self::File(path, err) => write!(f, "\"{path}\": {err}"),
+            Self::Parse(message) => write!(f, "{message}"),
+            Self::PairingImpossible(system) => write!(f, "No valid {} pairing exists", system.name()),
+            Self::InvalidCommand(message) => write!(f, "{message}"),
+            Self::UnknownPlayer(name) => write!(f, "Could not find player \"{name}\""),
+            Self::AmbiguousPlayer(name, candidates) => write!(f, "\"{name}\" could be any of {}", candidates.join(", ")),
+            Self::UnknownBoard(board_number) => write!(f, "No active game at board {board_number}"),
+            Self::UnknownSection(name) => write!(f, "Could not find section \"{name}\""),
+            Self::TournamentNotStarted => write!(f, "Tournament has not started"),
+            Self::TournamentFinished => write!(f, "Tournament is finished"),
+            Self::AllRoundsPaired(total_rounds) => write!(f, "All {total_rounds} rounds have been paired"),
+            Self::GamesPending(board_numbers) => {
+
+                let boards: Vec<_> = board_numbers.iter()
+                    .map(|board_number| board_number.to_string())
+                    .collect();
+
+                write!(f, "Games are still ongoing on boards {}", boards.join(", "))
+
+            }
+        }
+    }
+}
+
+impl Error {
+
+    /// For `map_err` on file operations, so the message names the file.
+    pub fn file(path: &str) -> impl FnOnce(std::io::Error) -> Self {
+        let path = path.to_string();
+        move |err| Self::File(path, err)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
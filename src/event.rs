@@ -0,0 +1,422 @@
+use crate::*;
+
+use html::{escape, page};
+
+use serde::{Deserialize, Serialize};
+
+// board numbers set aside for each section by default
+const SECTION_BOARDS: u32 = 100;
+
+/// One independently paired group of players within an event, e.g. an under 1800 section.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Section {
+    /// The section's name is the tournament's name.
+    pub tournament: Tournament,
+    #[serde(default)]
+    pub min_rating: Option<u32>,
+    #[serde(default)]
+    pub max_rating: Option<u32>
+}
+
+impl Section {
+
+    /// Whether a player with the rating may play in the section, unrated players may play anywhere.
+    pub fn accepts(&self, rating: Option<u32>) -> bool {
+
+        let Some(rating) = rating
+        else {
+            return true;
+        };
+
+        self.min_rating.is_none_or(|min_rating| rating >= min_rating)
+            && self.max_rating.is_none_or(|max_rating| rating <= max_rating)
+
+    }
+
+    pub fn name(&self) -> &str {
+        &self.tournament.name
+    }
+
+    /// The name with the rating limits, e.g. `U1800 (max 1799)`.
+    pub fn description(&self) -> String {
+        match (self.min_rating, self.max_rating) {
+            (None, None) => self.name().to_string(),
+            (Some(min_rating), None) => format!("{} (min {min_rating})", self.name()),
+            (None, Some(max_rating)) => format!("{} (max {max_rating})", self.name()),
+            (Some(min_rating), Some(max_rating)) => format!("{} ({min_rating}-{max_rating})", self.name())
+        }
+    }
+}
+
+/// Several sections played at the same venue, sharing one set of board numbers
+/// in which each section has its own block.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Event {
+    #[serde(default)]
+    pub name: String,
+    pub sections: Vec<Section>
+}
+
+impl From<Tournament> for Event {
+    fn from(tournament: Tournament) -> Self {
+        Self {
+            name: tournament.name.clone(),
+            sections: vec![Section {
+                tournament,
+                min_rating: None,
+                max_rating: None
+            }]
+        }
+    }
+}
+
+impl Event {
+
+    /// Reads an event saved with [`Event::save`], or a single tournament saved with [`Tournament::save`].
+    pub fn load(path: &str) -> Result<Self, Error> {
+
+        let invalid_file = |err: serde_json::Error| Error::Parse(format!("Invalid tournament file \"{path}\": {err}"));
+        let value: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path).map_err(Error::file(path))?).map_err(invalid_file)?;
+
+        let mut event: Self = if value.get("sections").is_some() {
+            serde_json::from_value(value).map_err(invalid_file)?
+        }
+        else {
+            serde_json::from_value::<Tournament>(value).map(Self::from).map_err(invalid_file)?
+        };
+
+        // files saved before pairing numbers existed
+        for section in event.sections.iter_mut().filter(|section| section.tournament.started()) {
+            section.tournament.assign_pairing_numbers();
+        }
+
+        // or before sections had their own boards
+        for (idx, section) in event.sections.iter_mut().enumerate() {
+            section.tournament.board_rules.first_board.get_or_insert(1 + SECTION_BOARDS * idx as u32);
+        }
+
+        Ok(event)
+
+    }
+
+    /// Saves the event as JSON.
+    pub fn save(&self, path: &str) -> Result<(), Error> {
+
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|err| Error::Parse(err.to_string()))?;
+
+        std::fs::write(path, json).map_err(Error::file(path))?;
+
+        Ok(())
+
+    }
+
+    /// The index of the section with the given name, ignoring case.
+    pub fn section_index(&self, name: &str) -> Result<usize, Error> {
+        self.sections.iter()
+            .position(|section| section.name().to_lowercase() == name.trim().to_lowercase())
+            .ok_or_else(|| Error::UnknownSection(name.to_string()))
+    }
+
+    /// Adds an empty section, returning its index. Its boards start at the next hundred after the last section's first board.
+    pub fn add_section(&mut self, name: String, min_rating: Option<u32>, max_rating: Option<u32>) -> Result<usize, Error> {
+
+        if self.section_index(&name).is_ok() {
+            return Err(Error::InvalidCommand(format!("There already is a section called \"{name}\"")));
+        }
+
+        let mut tournament = Tournament::new();
+        tournament.name = name;
+        tournament.board_rules.first_board = Some(self.next_first_board());
+
+        self.sections.push(Section {
+            tournament,
+            min_rating,
+            max_rating
+        });
+
+        Ok(self.sections.len() - 1)
+
+    }
+
+    /// The section with the narrowest rating limits that allow the rating, the first one for unrated players.
+    pub fn section_for_rating(&self, rating: Option<u32>) -> Option<usize> {
+
+        let range = |section: &Section| section.max_rating.unwrap_or(u32::MAX).saturating_sub(section.min_rating.unwrap_or(0));
+
+        self.sections.iter()
+            .enumerate()
+            .filter(|(_, section)| section.accepts(rating))
+            .min_by_key(|(idx, section)| if rating.is_some() { (range(section), *idx) } else { (0, *idx) })
+            .map(|(idx, _)| idx)
+
+    }
+
+    /// Adds a player to a section, checking its rating limits.
+    pub fn add_player(&mut self, section_idx: usize, name: String, rating: Option<u32>) -> Result<(), Error> {
+
+        let section = &mut self.sections[section_idx];
+
+        if !section.accepts(rating) {
+            return Err(Error::Parse(format!("A rating of {} is not allowed in section {}", rating.unwrap_or_default(), section.description())));
+        }
+
+        section.tournament.add_player(name, rating);
+
+        Ok(())
+
+    }
+
+    // the first board of a new section
+    fn next_first_board(&self) -> u32 {
+        self.sections.iter()
+            .map(|section| section.tournament.board_rules.first_board())
+            .max()
+            .map_or(1, |first_board| (first_board - 1) / SECTION_BOARDS * SECTION_BOARDS + SECTION_BOARDS + 1)
+    }
+
+    /// Pairs the next round of a section, numbering its boards as in [`Event::number_boards`].
+    pub fn pair_next_round(&mut self, section_idx: usize) -> Result<PairingSystem, Error> {
+
+        let used_system = self.sections[section_idx].tournament.pair_next_round()?;
+        self.number_boards(section_idx, self.sections[section_idx].tournament.rounds.len() - 1);
+
+        Ok(used_system)
+
+    }
+
+    /// Numbers the boards of a section's round (0-indexed) from the section's first board, so they don't depend
+    /// on the other sections. Boards fixed for other sections and those their games of the same round use are skipped.
+    pub fn number_boards(&mut self, section_idx: usize, round_idx: usize) {
+
+        let reserved: Vec<_> = self.sections.iter()
+            .enumerate()
+            .filter(|(idx, _)| *idx != section_idx)
+            .flat_map(|(_, section)| {
+
+                let tournament = &section.tournament;
+                let fixed_boards = tournament.board_rules.fixed.iter().map(|(_, board)| *board);
+                let used_boards = tournament.rounds.get(round_idx).into_iter().flat_map(|round| round.games.iter().map(|game| game.board_number));
+
+                fixed_boards.chain(used_boards)
+
+            })
+            .collect();
+
+        let tournament = &mut self.sections[section_idx].tournament;
+        let first_board = tournament.board_rules.first_board();
+
+        tournament.number_boards(round_idx, first_board, &reserved);
+
+    }
+
+    /// The section whose current round has the board, checking `preferred_section` first.
+    pub fn find_board(&self, board_number: u32, preferred_section: usize) -> Result<usize, Error> {
+
+        let has_board = |section: &Section| {
+            section.tournament.current_round()
+                .is_some_and(|round| round.games.iter().any(|game| game.board_number == board_number))
+        };
+
+        if self.sections.get(preferred_section).is_some_and(has_board) {
+            return Ok(preferred_section);
+        }
+
+        self.sections.iter()
+            .position(has_board)
+            .ok_or(Error::UnknownBoard(board_number))
+
+    }
+
+    /// The section that played the board in a round (0-indexed), checking `preferred_section` first.
+    pub fn find_board_in_round(&self, round_idx: usize, board_number: u32, preferred_section: usize) -> Result<usize, Error> {
+
+        let has_board = |section: &Section| {
+            section.tournament.rounds.get(round_idx)
+                .is_some_and(|round| round.games.iter().any(|game| game.board_number == board_number))
+        };
+
+        if self.sections.get(preferred_section).is_some_and(has_board) {
+            return Ok(preferred_section);
+        }
+
+        self.sections.iter()
+            .position(has_board)
+            .ok_or(Error::UnknownBoard(board_number))
+
+    }
+
+    /// Sets the result of a game in the current round of whichever section plays on the board.
+    pub fn report_result(&mut self, board_number: u32, result: GameResult) -> Result<(), Error> {
+
+        let section_idx = self.find_board(board_number, 0)?;
+        self.sections[section_idx].tournament.report_result(board_number, result)
+
+    }
+
+    /// Reports several results at once, finding the section of each board as in [`Event::find_board`].
+    /// Nothing is changed unless every board exists and no section involved has finished.
+    pub fn report_results(&mut self, results: &[(u32, GameResult)], preferred_section: usize) -> Result<(), Error> {
+
+        let mut section_results = vec![Vec::new(); self.sections.len()];
+
+        for &(board_number, result) in results.iter() {
+
+            let section_idx = self.find_board(board_number, preferred_section)?;
+
+            if self.sections[section_idx].tournament.finished {
+                return Err(Error::TournamentFinished);
+            }
+
+            section_results[section_idx].push((board_number, result));
+
+        }
+
+        for (section, results) in self.sections.iter_mut().zip(section_results) {
+            if !results.is_empty() {
+                section.tournament.report_results(&results)?;
+            }
+        }
+
+        Ok(())
+
+    }
+
+    /// Boards of every section's current round still waiting for a result, in order.
+    pub fn pending_boards(&self) -> Vec<u32> {
+
+        let mut pending_boards: Vec<_> = self.sections.iter()
+            .filter_map(|section| section.tournament.current_round())
+            .flat_map(Round::pending_boards)
+            .collect();
+
+        pending_boards.sort_unstable();
+
+        pending_boards
+
+    }
+
+    /// A standalone HTML page with the standings, crosstable and latest pairings of every section.
+    pub fn to_html(&self) -> String {
+
+        let title = if self.name.is_empty() {
+            "Event".to_string()
+        }
+        else {
+            escape(&self.name)
+        };
+
+        let mut body = format!("<h1>{title}</h1>\n");
+
+        for section in self.sections.iter() {
+
+            let tournament = &section.tournament;
+
+            body += &format!("<h2>{}</h2>\n", escape(&section.description()));
+
+            if tournament.rounds.is_empty() {
+                body += "<p>No rounds have been paired.</p>\n";
+                continue;
+            }
+
+            body += &tournament.round_html(tournament.rounds.len() - 1);
+            body += &tournament.standings_html();
+
+            for &category in tournament.categories.iter() {
+                body += &tournament.category_standings_html(category, tournament.year());
+            }
+
+            body += &tournament.crosstable().as_html(&tournament.players);
+
+        }
+
+        page(&title, html::STYLE, &body)
+
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    fn event() -> Event {
+
+        let mut event = Event::default();
+
+        for (name, max_rating) in [("Open", None), ("U1800", Some(1799))] {
+
+            let section_idx = event.add_section(name.to_string(), None, max_rating).unwrap();
+
+            for idx in 0..4 {
+                event.add_player(section_idx, format!("{name} {idx}"), Some(1700 - idx * 10)).unwrap();
+            }
+        }
+
+        event
+
+    }
+
+    fn board_numbers(event: &Event, section_idx: usize) -> Vec<u32> {
+        event.sections[section_idx].tournament.current_round().unwrap().games.iter()
+            .map(|game| game.board_number)
+            .collect()
+    }
+
+    #[test]
+    fn board_numbers_do_not_depend_on_pairing_order() {
+
+        let mut event = event();
+
+        event.pair_next_round(1).unwrap();
+        event.pair_next_round(0).unwrap();
+
+        assert_eq!(board_numbers(&event, 0), [1, 2]);
+        assert_eq!(board_numbers(&event, 1), [101, 102]);
+
+    }
+
+    #[test]
+    fn boards_fixed_in_another_section_are_kept_free() {
+
+        let mut event = event();
+        event.sections[1].tournament.board_rules.fix(0, 1);
+
+        event.pair_next_round(0).unwrap();
+        event.pair_next_round(1).unwrap();
+
+        assert_eq!(board_numbers(&event, 0), [2, 3]);
+        assert_eq!(board_numbers(&event, 1), [1, 101]);
+
+    }
+
+    #[test]
+    fn only_sections_saved_without_a_first_board_are_given_one() {
+
+        let path = std::env::temp_dir().join(format!("swiss-event-{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        let first_boards = |event: &Event| -> Vec<u32> {
+            event.sections.iter()
+                .map(|section| section.tournament.board_rules.first_board())
+                .collect()
+        };
+
+        let mut event = event();
+        event.sections[1].tournament.board_rules.first_board = Some(1);
+        event.save(path).unwrap();
+
+        assert_eq!(first_boards(&Event::load(path).unwrap()), [1, 1]);
+
+        for section in event.sections.iter_mut() {
+            section.tournament.board_rules.first_board = None;
+        }
+
+        event.save(path).unwrap();
+        let loaded = Event::load(path);
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(first_boards(&loaded.unwrap()), [1, 101]);
+
+    }
+}
@@ -0,0 +1,323 @@
+use crate::*;
+
+use csv::{csv_field, number_field};
+use html::{escape, withdrawn_class};
+
+use rand::Rng;
+use serde_json::{json, Value};
+
+/// How often one player finished where over all simulations.
+pub struct ForecastRow {
+    pub player: PlayerID,
+    /// Indexed by place - 1, shared places count for every player sharing them.
+    pub place_counts: Vec<usize>,
+    pub average_score: f32,
+    /// Simulations in which the player won any prize money.
+    pub prize_count: usize,
+    pub average_prize: f64
+}
+
+/// The predicted final standings from playing out the rest of a tournament many times.
+pub struct Forecast {
+    /// Most likely winner first.
+    pub rows: Vec<ForecastRow>,
+    /// Simulations played to the end, which the counts are taken from.
+    pub num_simulations: usize,
+    /// Simulations abandoned because a round couldn't be paired.
+    pub num_unpaired: usize,
+    pub rounds_remaining: usize,
+    /// Whether a prize fund was given, otherwise the prize columns are left out.
+    pub with_prizes: bool
+}
+
+impl Tournament {
+
+    /// Plays out the pending games of the current round and `rounds_remaining` further rounds `num_simulations` times,
+    /// pairing each round with the tournament's pairing system and picking results by rating.
+    /// No more rounds are played than the tournament has left, and simulations reaching a round that can't be paired are left out.
+    /// Prizes are handed out from `fund` after every simulation, counting ages against `year`.
+    pub fn forecast(&self, rounds_remaining: usize, num_simulations: usize, fund: Option<&PrizeFund>, year: u32, rng: &mut impl Rng) -> Result<Forecast, Error> {
+
+        let rounds_remaining = match self.rounds_remaining() {
+            _ if self.finished => 0,
+            Some(planned_rounds_remaining) => rounds_remaining.min(planned_rounds_remaining),
+            None => rounds_remaining
+        };
+
+        let num_players = self.players.len();
+        let mut num_unpaired = 0;
+
+        let mut place_counts = vec![vec![0; num_players]; num_players];
+        let mut total_scores = vec![0.0; num_players];
+        let mut prize_counts = vec![0; num_players];
+        let mut total_prizes = vec![0.0; num_players];
+
+        'simulations: for _ in 0..num_simulations {
+
+            let mut simulation = self.clone();
+
+            for round_idx in 0..=rounds_remaining {
+
+                // the first pass only finishes the current round
+                if round_idx > 0 {
+                    match simulation.pair_next_round() {
+                        Ok(_) => {}
+                        Err(Error::PairingImpossible(_)) => {
+                            num_unpaired += 1;
+                            continue 'simulations;
+                        }
+                        Err(err) => return Err(err)
+                    }
+                }
+
+                if let Some(round) = simulation.rounds.last_mut() {
+                    round.simulate_results(&simulation.players, rng);
+                }
+            }
+
+            for standing in simulation.standings() {
+                place_counts[standing.player][standing.place - 1] += 1;
+                total_scores[standing.player] += standing.score;
+            }
+
+            if let Some(fund) = fund {
+
+                let awards = fund.distribute(&simulation, year);
+
+                for player in 0..num_players {
+
+                    let amount: f64 = awards.iter()
+                        .filter(|award| award.player == player)
+                        .map(|award| award.amount)
+                        .sum();
+
+                    if amount > 0.0 {
+                        prize_counts[player] += 1;
+                        total_prizes[player] += amount;
+                    }
+                }
+            }
+        }
+
+        let num_simulations = num_simulations - num_unpaired;
+        let divisor = num_simulations.max(1);
+
+        let mut rows: Vec<_> = place_counts.into_iter()
+            .enumerate()
+            .map(|(player, place_counts)| ForecastRow {
+                player,
+                place_counts,
+                average_score: total_scores[player] / divisor as f32,
+                prize_count: prize_counts[player],
+                average_prize: total_prizes[player] / divisor as f64
+            })
+            .collect();
+
+        rows.sort_by(|row1, row2| row2.place_counts[..].cmp(&row1.place_counts[..])
+            .then(row2.average_score.total_cmp(&row1.average_score)));
+
+        Ok(Forecast {
+            rows,
+            num_simulations,
+            num_unpaired,
+            rounds_remaining,
+            with_prizes: fund.is_some()
+        })
+    }
+}
+
+impl Forecast {
+
+    fn percentage(&self, count: usize) -> f64 {
+        count as f64 * 100.0 / self.num_simulations.max(1) as f64
+    }
+
+    pub fn as_string(&self, players: &[Player]) -> String {
+
+        let name_width = self.rows.iter()
+            .map(|row| players[row.player].label().chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+
+        let mut output = format!("{:<name_width$}  Avg score     1st   Top 3", "Name");
+
+        if self.with_prizes {
+            output += "   Prize  Avg prize";
+        }
+
+        output += "\n";
+
+        for row in self.rows.iter() {
+
+            output += &format!(
+                "{:<name_width$}  {:>9.2}  {:>5.1}%  {:>5.1}%",
+                players[row.player].label(),
+                row.average_score,
+                self.percentage(row.place_counts[0]),
+                self.percentage(row.place_counts.iter().take(3).sum())
+            );
+
+            if self.with_prizes {
+                output += &format!("  {:>5.1}%  {:>9.2}", self.percentage(row.prize_count), row.average_prize);
+            }
+
+            output += "\n";
+
+        }
+
+        output += &format!("{} simulations of {} remaining rounds", self.num_simulations, self.rounds_remaining);
+
+        if self.num_unpaired > 0 {
+            output += &format!(", {} more left out as a round couldn't be paired", self.num_unpaired);
+        }
+
+        output += "\n";
+
+        output
+
+    }
+
+    /// One column per place with the chance of finishing there, in percent.
+    pub fn as_csv(&self, players: &[Player]) -> String {
+
+        let mut output = "pairing_number,name,average_score".to_string();
+
+        for place in 1..=players.len() {
+            output += &format!(",place_{place}");
+        }
+
+        if self.with_prizes {
+            output += ",prize,average_prize";
+        }
+
+        output += "\n";
+
+        for row in self.rows.iter() {
+
+            let player = &players[row.player];
+
+            output += &format!("{},{}", number_field(player), csv_field(&player.name));
+
+            output += &format!(",{}", row.average_score);
+
+            for &count in row.place_counts.iter() {
+                output += &format!(",{}", self.percentage(count));
+            }
+
+            if self.with_prizes {
+                output += &format!(",{},{}", self.percentage(row.prize_count), row.average_prize);
+            }
+
+            output += "\n";
+
+        }
+
+        output
+
+    }
+
+    pub fn as_json(&self, players: &[Player]) -> Value {
+
+        let rows: Vec<_> = self.rows.iter()
+            .map(|row| {
+
+                let place_chances: Vec<_> = row.place_counts.iter()
+                    .map(|&count| self.percentage(count))
+                    .collect();
+
+                let mut value = json!({
+                    "id": row.player,
+                    "pairing_number": players[row.player].pairing_number,
+                    "name": players[row.player].name,
+                    "rating": players[row.player].rating,
+                    "average_score": row.average_score,
+                    "place_chances": place_chances
+                });
+
+                if self.with_prizes {
+                    value["prize_chance"] = json!(self.percentage(row.prize_count));
+                    value["average_prize"] = json!(row.average_prize);
+                }
+
+                value
+
+            })
+            .collect();
+
+        json!({
+            "simulations": self.num_simulations,
+            "unpaired_simulations": self.num_unpaired,
+            "rounds_remaining": self.rounds_remaining,
+            "players": rows
+        })
+    }
+
+    /// A `<table>` of average scores and the chances of winning and of finishing in the top 3, in percent.
+    pub fn as_html(&self, players: &[Player]) -> String {
+
+        let mut html = "<table>\n<tr><th>Name</th><th>Avg score</th><th>1st</th><th>Top 3</th>".to_string();
+
+        if self.with_prizes {
+            html += "<th>Prize</th><th>Avg prize</th>";
+        }
+
+        html += "</tr>\n";
+
+        for row in self.rows.iter() {
+
+            let player = &players[row.player];
+
+            html += &format!(
+                "<tr{}><td>{}</td><td class=\"number\">{:.2}</td><td class=\"number\">{:.1}%</td><td class=\"number\">{:.1}%</td>",
+                withdrawn_class(player),
+                escape(&player.label()),
+                row.average_score,
+                self.percentage(row.place_counts[0]),
+                self.percentage(row.place_counts.iter().take(3).sum())
+            );
+
+            if self.with_prizes {
+                html += &format!("<td class=\"number\">{:.1}%</td><td class=\"number\">{:.2}</td>", self.percentage(row.prize_count), row.average_prize);
+            }
+
+            html += "</tr>\n";
+
+        }
+
+        html += "</table>\n";
+
+        html
+
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn rounds_are_clamped_and_unpairable_simulations_left_out() {
+
+        let mut tournament = Tournament::new();
+        tournament.total_rounds = Some(3);
+        tournament.add_player("Ann".to_string(), Some(1800));
+        tournament.pair_next_round().unwrap();
+
+        // a lone player can't get a second bye
+        let forecast = tournament.forecast(10, 20, None, 2026, &mut StdRng::seed_from_u64(1)).unwrap();
+
+        assert_eq!(forecast.rounds_remaining, 2);
+        assert_eq!(forecast.num_unpaired, 20);
+        assert_eq!(forecast.num_simulations, 0);
+
+        let forecast = tournament.forecast(0, 20, None, 2026, &mut StdRng::seed_from_u64(1)).unwrap();
+
+        assert_eq!(forecast.num_unpaired, 0);
+        assert_eq!(forecast.rows.iter().map(|row| row.place_counts[0]).sum::<usize>(), 20);
+
+    }
+}
@@ -0,0 +1,231 @@
+use crate::*;
+
+use category::Category;
+
+pub(crate) const STYLE: &str = "body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { border: 1px solid #999; padding: 0.2em 0.6em; }
+th { background: #eee; }
+td.number { text-align: right; }
+td.result { text-align: center; }
+tr.withdrawn { color: #888; }";
+
+impl Tournament {
+
+    /// A standalone HTML page with the standings, a crosstable and the pairings of every round, latest first.
+    pub fn to_html(&self) -> String {
+
+        let mut html = format!("<h1>{}</h1>\n", self.html_title());
+
+        if self.rounds.is_empty() {
+            html += "<p>No rounds have been paired.</p>\n";
+        }
+        else if self.finished {
+            html += "<p>Final standings</p>\n";
+        }
+        else if let Some(total_rounds) = self.total_rounds {
+            html += &format!("<p>After round {} of {total_rounds}</p>\n", self.rounds.len());
+        }
+        else {
+            html += &format!("<p>After round {}</p>\n", self.rounds.len());
+        }
+
+        html += &self.standings_html();
+
+        let year = self.year();
+
+        for &category in self.categories.iter() {
+            html += &self.category_standings_html(category, year);
+        }
+
+        html += "<h2>Crosstable</h2>\n";
+        html += &self.crosstable().as_html(&self.players);
+
+        for round_idx in (0..self.rounds.len()).rev() {
+            html += &self.round_html(round_idx);
+        }
+
+        page(&self.html_title(), STYLE, &html)
+
+    }
+
+    /// Who plays where in a round (0-indexed) as a `<table>`, ordered by name.
+    pub fn alphabetical_html(&self, round_idx: usize) -> Option<String> {
+
+        let mut html = "<table>\n<tr><th>Name</th><th>Board</th><th>Colour</th><th>Opponent</th></tr>\n".to_string();
+
+        for (player, seat) in self.alphabetical_pairings(round_idx)? {
+
+            let name = escape(&self.players[player].label());
+
+            html += &match seat {
+                Seat::Board { board, white, opponent, .. } => format!(
+                    "<tr><td>{name}</td><td class=\"number\">{board}</td><td>{}</td><td>{}</td></tr>\n",
+                    if white { "White" } else { "Black" },
+                    escape(&self.players[opponent].label())
+                ),
+                Seat::Bye => format!("<tr><td>{name}</td><td></td><td></td><td>bye</td></tr>\n")
+            };
+        }
+
+        html += "</table>\n";
+
+        Some(html)
+
+    }
+
+    /// Every round of one player as a `<table>`.
+    pub fn history_html(&self, player: PlayerID) -> String {
+
+        let mut html = "<table>\n<tr><th>Round</th><th>Board</th><th>Colour</th><th>Opponent</th><th>Result</th></tr>\n".to_string();
+
+        for (round_idx, seat) in self.history(player).into_iter().enumerate() {
+
+            html += &match seat {
+                Some(Seat::Board { board, white, opponent, result }) => format!(
+                    "<tr><td class=\"number\">{}</td><td class=\"number\">{board}</td><td>{}</td><td>{}</td><td class=\"result\">{}</td></tr>\n",
+                    round_idx + 1,
+                    if white { "White" } else { "Black" },
+                    escape(&self.players[opponent].label()),
+                    result.as_letter()
+                ),
+                Some(Seat::Bye) => format!("<tr><td class=\"number\">{}</td><td></td><td></td><td>bye</td><td class=\"result\">W</td></tr>\n", round_idx + 1),
+                None => format!("<tr><td class=\"number\">{}</td><td></td><td></td><td>not paired</td><td></td></tr>\n", round_idx + 1)
+            };
+        }
+
+        html += "</table>\n";
+
+        html
+
+    }
+
+    /// The escaped tournament name, or a placeholder if it has none.
+    pub(crate) fn html_title(&self) -> String {
+        if self.name.is_empty() {
+            "Tournament".to_string()
+        }
+        else {
+            escape(&self.name)
+        }
+    }
+
+    /// The standings as a headed `<table>`.
+    pub fn standings_html(&self) -> String {
+
+        let mut html = "<h2>Standings</h2>\n<table>\n".to_string();
+        html += "<tr><th>#</th><th>Name</th><th>Rating</th><th>Score</th><th>SB</th><th>W</th><th>D</th><th>L</th><th>B</th></tr>\n";
+
+        for standing in self.standings() {
+
+            let player = &self.players[standing.player];
+
+            html += &format!(
+                "<tr{}><td class=\"number\">{}</td><td>{}</td><td class=\"number\">{}</td><td class=\"number\">{:.1}</td><td class=\"number\">{:.2}</td><td class=\"number\">{}</td><td class=\"number\">{}</td><td class=\"number\">{}</td><td class=\"number\">{}</td></tr>\n",
+                withdrawn_class(player),
+                standing.place,
+                escape(&player.label()),
+                player.rating.map(|rating| rating.to_string()).unwrap_or_default(),
+                standing.score,
+                standing.sonneborn_berger,
+                standing.wins,
+                standing.draws,
+                standing.losses,
+                standing.byes
+            );
+        }
+
+        html += "</table>\n";
+
+        html
+
+    }
+
+    /// The standings of one category as a headed `<table>`, with each player's overall place.
+    pub fn category_standings_html(&self, category: Category, year: u32) -> String {
+
+        let mut html = format!("<h2>{} Standings</h2>\n<table>\n", escape(&category.name()));
+        html += "<tr><th>#</th><th>Overall</th><th>Name</th><th>Rating</th><th>Score</th><th>SB</th></tr>\n";
+
+        for category_standing in self.category_standings(category, year) {
+
+            let standing = &category_standing.standing;
+            let player = &self.players[standing.player];
+
+            html += &format!(
+                "<tr{}><td class=\"number\">{}</td><td class=\"number\">{}</td><td>{}</td><td class=\"number\">{}</td><td class=\"number\">{:.1}</td><td class=\"number\">{:.2}</td></tr>\n",
+                withdrawn_class(player),
+                category_standing.place,
+                standing.place,
+                escape(&player.label()),
+                player.rating.map(|rating| rating.to_string()).unwrap_or_default(),
+                standing.score,
+                standing.sonneborn_berger
+            );
+        }
+
+        html += "</table>\n";
+
+        html
+
+    }
+
+    /// The pairings and results of one round as a headed `<table>`.
+    pub fn round_html(&self, round_idx: usize) -> String {
+
+        let round = &self.rounds[round_idx];
+        let name = |id: PlayerID| escape(&self.players[id].label());
+
+        let mut html = format!("<h2>Round {}</h2>\n<table>\n", round_idx + 1);
+        html += "<tr><th>Board</th><th>White</th><th>Result</th><th>Black</th></tr>\n";
+
+        for game in round.games.iter() {
+
+            let result = if game.result == GameResult::Pending {
+                ""
+            }
+            else {
+                game.result.as_notation()
+            };
+
+            html += &format!(
+                "<tr><td class=\"number\">{}</td><td>{}</td><td class=\"result\">{result}</td><td>{}</td></tr>\n",
+                game.board_number,
+                name(game.white_player),
+                name(game.black_player)
+            );
+        }
+
+        if let Some(bye_player) = round.bye_player {
+            html += &format!("<tr><td></td><td>{}</td><td class=\"result\">bye</td><td></td></tr>\n", name(bye_player));
+        }
+
+        html += "</table>\n";
+
+        html
+
+    }
+}
+
+// a complete document around already escaped `title` and `body`
+pub(crate) fn page(title: &str, style: &str, body: &str) -> String {
+    format!("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n{style}\n</style>\n</head>\n<body>\n{body}</body>\n</html>\n")
+}
+
+/// The class attribute for a table row of a player, marking withdrawn players.
+pub(crate) fn withdrawn_class(player: &Player) -> &'static str {
+    if player.active {
+        ""
+    }
+    else {
+        " class=\"withdrawn\""
+    }
+}
+
+/// Escapes text for use in HTML content and attribute values.
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
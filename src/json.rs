@@ -0,0 +1,208 @@
+use crate::*;
+
+use category::Category;
+
+use serde_json::{json, Value};
+
+/// JSON views of a tournament, referring to players by name as well as ID.
+impl Tournament {
+
+    pub fn players_json(&self) -> Value {
+
+        let scores = self.get_player_scores();
+
+        let players: Vec<_> = self.players.iter()
+            .enumerate()
+            .map(|(id, player)| json!({
+                "id": id,
+                "pairing_number": player.pairing_number,
+                "name": player.name,
+                "title": player.title.map(Title::name),
+                "rating": player.rating,
+                "active": player.active,
+                "score": scores[id]
+            }))
+            .collect();
+
+        json!(players)
+
+    }
+
+    pub fn round_json(&self, round_idx: usize) -> Option<Value> {
+
+        let round = self.rounds.get(round_idx)?;
+        let name = |id: PlayerID| self.players[id].name.as_str();
+        let number = |id: PlayerID| self.players[id].pairing_number;
+
+        let games: Vec<_> = round.games.iter()
+            .map(|game| json!({
+                "board": game.board_number,
+                "white": name(game.white_player),
+                "white_id": game.white_player,
+                "white_number": number(game.white_player),
+                "black": name(game.black_player),
+                "black_id": game.black_player,
+                "black_number": number(game.black_player),
+                "result": game.result.as_notation()
+            }))
+            .collect();
+
+        Some(json!({
+            "round": round_idx + 1,
+            "games": games,
+            "bye": round.bye_player.map(name),
+            "bye_id": round.bye_player,
+            "bye_number": round.bye_player.and_then(number)
+        }))
+    }
+
+    pub fn rounds_json(&self) -> Value {
+        json!((0..self.rounds.len()).filter_map(|idx| self.round_json(idx)).collect::<Vec<_>>())
+    }
+
+    pub fn standings_json(&self) -> Value {
+
+        let rows: Vec<_> = self.standings().iter()
+            .map(|standing| {
+
+                let player = &self.players[standing.player];
+
+                json!({
+                    "place": standing.place,
+                    "id": standing.player,
+                    "pairing_number": player.pairing_number,
+                    "name": player.name,
+                    "rating": player.rating,
+                    "score": standing.score,
+                    "sonneborn_berger": standing.sonneborn_berger,
+                    "wins": standing.wins,
+                    "draws": standing.draws,
+                    "losses": standing.losses,
+                    "byes": standing.byes,
+                    "active": player.active
+                })
+            })
+            .collect();
+
+        json!(rows)
+
+    }
+
+    /// The standings of one category, each row with its place in the category and overall.
+    pub fn category_standings_json(&self, category: Category, year: u32) -> Value {
+
+        let rows: Vec<_> = self.category_standings(category, year).iter()
+            .map(|category_standing| {
+
+                let standing = &category_standing.standing;
+                let player = &self.players[standing.player];
+
+                json!({
+                    "place": category_standing.place,
+                    "overall_place": standing.place,
+                    "id": standing.player,
+                    "pairing_number": player.pairing_number,
+                    "name": player.name,
+                    "rating": player.rating,
+                    "score": standing.score,
+                    "sonneborn_berger": standing.sonneborn_berger,
+                    "active": player.active
+                })
+            })
+            .collect();
+
+        json!({
+            "category": category.name(),
+            "standings": rows
+        })
+    }
+
+    pub fn crosstable_json(&self) -> Value {
+
+        let rows: Vec<_> = self.crosstable().rows.iter()
+            .map(|row| json!({
+                "rank": row.rank,
+                "id": row.player,
+                "pairing_number": self.players[row.player].pairing_number,
+                "name": self.players[row.player].name,
+                "rounds": row.entries.iter().map(|entry| entry.as_string()).collect::<Vec<_>>(),
+                "score": row.score,
+                "sonneborn_berger": row.sonneborn_berger
+            }))
+            .collect();
+
+        json!(rows)
+
+    }
+
+    pub fn alphabetical_json(&self, round_idx: usize) -> Option<Value> {
+
+        let rows: Vec<_> = self.alphabetical_pairings(round_idx)?.into_iter()
+            .map(|(player, seat)| seat_json(self, player, Some(seat)))
+            .collect();
+
+        Some(json!(rows))
+
+    }
+
+    pub fn history_json(&self, player: PlayerID) -> Value {
+
+        let rounds: Vec<_> = self.history(player).into_iter()
+            .enumerate()
+            .map(|(round_idx, seat)| {
+
+                let mut row = seat_json(self, player, seat);
+
+                if let Some(fields) = row.as_object_mut() {
+                    fields.remove("id");
+                    fields.remove("name");
+                    fields.remove("pairing_number");
+                    fields.insert("round".to_string(), json!(round_idx + 1));
+                }
+
+                row
+
+            })
+            .collect();
+
+        json!({
+            "id": player,
+            "pairing_number": self.players[player].pairing_number,
+            "name": self.players[player].name,
+            "rounds": rounds
+        })
+    }
+}
+
+fn seat_json(tournament: &Tournament, player: PlayerID, seat: Option<Seat>) -> Value {
+
+    let name = &tournament.players[player].name;
+    let number = tournament.players[player].pairing_number;
+
+    match seat {
+        Some(Seat::Board { board, white, opponent, result }) => json!({
+            "id": player,
+            "pairing_number": number,
+            "name": name,
+            "board": board,
+            "colour": if white { "white" } else { "black" },
+            "opponent": tournament.players[opponent].name,
+            "opponent_id": opponent,
+            "opponent_number": tournament.players[opponent].pairing_number,
+            "result": (result != GameResult::Pending).then(|| result.as_letter().to_string()),
+            "bye": false
+        }),
+        Some(Seat::Bye) => json!({
+            "id": player,
+            "pairing_number": number,
+            "name": name,
+            "bye": true
+        }),
+        None => json!({
+            "id": player,
+            "pairing_number": number,
+            "name": name,
+            "bye": false
+        })
+    }
+}
@@ -0,0 +1,73 @@
+//! Swiss-system tournament pairing engine.
+//!
+//! A [`Tournament`] holds the players and every [`Round`] played so far. Rounds are
+//! paired with one of the [`PairingSystem`]s, results are reported per board, and
+//! [`Tournament::standings`] ranks the players.
+//!
+//! ```
+//! use swiss::*;
+//!
+//! let mut tournament = Tournament::new();
+//!
+//! tournament.add_player("Alice".to_string(), Some(1800));
+//! tournament.add_player("Bob".to_string(), Some(1600));
+//!
+//! tournament.system = PairingSystem::Dutch;
+//! tournament.pair_next_round()?;
+//! tournament.report_result(1, GameResult::Draw)?;
+//!
+//! let standings = tournament.standings();
+//! assert_eq!(standings[0].score, 0.5);
+//! # Ok::<(), Error>(())
+//! ```
+
+pub mod player;
+pub mod round;
+pub mod pairing;
+pub mod tournament;
+pub mod algorithms;
+pub mod error;
+pub mod checker;
+pub mod standings;
+pub mod rtg;
+pub mod trf;
+pub mod json;
+pub mod html;
+pub mod text;
+pub mod csv;
+pub mod crosstable;
+pub mod pgn;
+pub mod listing;
+pub mod print;
+pub mod event;
+pub mod category;
+pub mod prize;
+pub mod forecast;
+pub mod contention;
+pub mod boards;
+pub mod edit;
+pub mod correction;
+pub mod pairing_numbers;
+#[cfg(test)]
+mod testing;
+#[cfg(feature = "server")]
+pub mod server;
+
+pub use player::*;
+pub use round::*;
+pub use tournament::*;
+pub use pairing::*;
+pub use error::*;
+pub use algorithms::PairingSystem;
+pub use checker::Violation;
+pub use standings::Standing;
+pub use crosstable::{Crosstable, CrosstableEntry, CrosstableRow};
+pub use pgn::PgnImport;
+pub use listing::Seat;
+pub use event::{Event, Section};
+pub use category::{Category, CategoryStanding};
+pub use prize::{Award, PrizeFund, TieRule};
+pub use forecast::{Forecast, ForecastRow};
+pub use contention::{Contention, ContentionRow, ContentionTable};
+pub use boards::BoardRules;
+pub use correction::{Correction, Reopening};
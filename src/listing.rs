@@ -0,0 +1,62 @@
+use crate::*;
+
+/// Where a player sits in a round.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Seat {
+    Board {
+        board: u32,
+        white: bool,
+        opponent: PlayerID,
+        /// From this player's point of view.
+        result: GameResult
+    },
+    Bye
+}
+
+impl Round {
+
+    /// The seat of the player, or `None` if they weren't paired.
+    pub fn seat(&self, player: PlayerID) -> Option<Seat> {
+
+        if self.bye_player == Some(player) {
+            return Some(Seat::Bye);
+        }
+
+        self.games.iter().find_map(|game| {
+            if game.white_player == player {
+                Some(Seat::Board { board: game.board_number, white: true, opponent: game.black_player, result: game.result })
+            }
+            else if game.black_player == player {
+                Some(Seat::Board { board: game.board_number, white: false, opponent: game.white_player, result: game.result.opposite() })
+            }
+            else {
+                None
+            }
+        })
+    }
+}
+
+impl Tournament {
+
+    /// Every player paired in the round (0-indexed) with their seat, ordered by name ignoring case.
+    pub fn alphabetical_pairings(&self, round_idx: usize) -> Option<Vec<(PlayerID, Seat)>> {
+
+        let round = self.rounds.get(round_idx)?;
+
+        let mut pairings: Vec<_> = self.get_all_player_ids().0.into_iter()
+            .filter_map(|player| Some((player, round.seat(player)?)))
+            .collect();
+
+        pairings.sort_by_cached_key(|(player, _)| self.players[*player].name.to_lowercase());
+
+        Some(pairings)
+
+    }
+
+    /// The player's seat in every round so far, `None` for rounds they weren't paired in.
+    pub fn history(&self, player: PlayerID) -> Vec<Option<Seat>> {
+        self.rounds.iter()
+            .map(|round| round.seat(player))
+            .collect()
+    }
+}
@@ -1,440 +1,995 @@
-mod player;
-mod round;
-mod pairing;
-mod tournament;
-mod algorithms;
-mod error;
-
-use player::*;
-use round::*;
-use tournament::*;
-use pairing::*;
-use error::*;
-
-use std::io::{Read, Write};
+mod cli;
+mod batch;
+#[cfg(feature = "tui")]
+mod tui;
+
+use swiss::*;
+use swiss::{prize, rtg};
+use cli::Format;
+
+use std::io::Write;
 use std::fs::File;
 
 fn main() {
 
-    let stdin = std::io::stdin();
-    let mut stdout = std::io::stdout();
-    let mut read_line = |prompt: &str, lower: bool| {
+    let args = std::env::args().collect::<Vec<_>>();
 
-        print!("{}", prompt);
-        let _ = stdout.flush();
-        
-        let mut line = String::new();
-        let _ = stdin.read_line(&mut line);
+    if let Some(exit_code) = cli::run_subcommand(&args) {
+        std::process::exit(exit_code);
+    }
 
-        if (lower) {
-            line = line.to_lowercase();
-        }
-        
-        line.to_lowercase().trim().to_owned()
+    let mut session = Session {
+        event: Event::from(Tournament::new()),
+        section_idx: 0
+    };
+
+    if args.len() > 1 {
 
+        println!("Reading player data from file: {}", args[1]);
+
+        if let Err(err) = session.tournament().add_players_from_file(&args[1]) {
+            println!("Error: {err}.");
+            std::process::exit(1);
+        }
     };
 
-    let args = std::env::args().collect::<Vec<_>>();
+    let mut input = Input::Stdin;
 
-    let mut tournament = Tournament::new();
+    while let Ok(command) = input.read_line("\n> ", false) {
+        if let Err(err) = run_command(&mut session, &command, &mut input) {
+            println!("Error: {err}.");
+        }
+    }
+}
 
-    if args.len() > 1 {
+/// The event being run from the prompt and the section that commands apply to.
+#[derive(Clone)]
+pub struct Session {
+    pub event: Event,
+    pub section_idx: usize
+}
 
-        println!("Reading player data from file: {}", args[1]);
-        
-        let Ok(mut file) = File::open(args[1].clone())
-        else {
-            error("File not found");
-        };
+impl Session {
 
-        let mut lines = String::new();
-        let _ = file.read_to_string(&mut lines);
+    pub fn tournament(&mut self) -> &mut Tournament {
+        &mut self.event.sections[self.section_idx].tournament
+    }
+}
 
-        for line in lines.split('\n') {
+/// Where commands and answers to prompts are read from.
+pub enum Input {
+    Stdin,
+    Script {
+        lines: Vec<String>,
+        next_line: usize,
+        dry_run: bool
+    }
+}
+
+impl Input {
+
+    pub fn script(text: &str, dry_run: bool) -> Self {
+        Self::Script {
+            lines: text.lines().map(|line| line.to_string()).collect(),
+            next_line: 0,
+            dry_run
+        }
+    }
+
+    // script lines are echoed after the prompt so the output reads like an interactive session
+    pub fn read_line(&mut self, prompt: &str, lower: bool) -> Result<String, Error> {
 
-            let mut parts = line.split(',');
+        let mut line = match self {
+            Self::Stdin => {
 
-            let name = parts.next().unwrap().trim().to_owned();
+                print!("{}", prompt);
+                let _ = std::io::stdout().flush();
 
-            let rating = parts.next().map(|string| {
+                let mut line = String::new();
+
+                if std::io::stdin().read_line(&mut line)? == 0 {
+                    return Err(Error::InvalidCommand("Unexpected end of input".to_string()));
+                }
+
+                line
+
+            }
+            Self::Script { lines, next_line, .. } => {
 
-                let Ok(rating_val) = string.trim().parse()
+                let Some(line) = lines.get(*next_line)
                 else {
-                    error(format!("Invalid elo \"{string}\""));
+                    return Err(Error::InvalidCommand("Unexpected end of script".to_string()));
                 };
 
-                rating_val
+                *next_line += 1;
+                println!("{prompt}{line}");
 
-            });
+                line.clone()
 
-            tournament.add_player(name, rating);
+            }
+        };
 
+        if lower {
+            line = line.to_lowercase();
         }
-    };
 
-    loop {
+        Ok(line.trim().to_owned())
 
-        let command = read_line("\n> ", true);
-        let split: Vec<_> = command.split(' ').collect();
+    }
 
-        match split[0] {
-            "add" => {
-                tournament.add_player(
-                    read_line("Name: ", false),
-                    read_line("Rating (leave blank for unknown): ", true).parse().ok()
-                );
-            }
-            "remove" => {
+    pub fn finished(&self) -> bool {
+        match self {
+            Self::Stdin => false,
+            Self::Script { lines, next_line, .. } => *next_line >= lines.len()
+        }
+    }
 
-                let name = if split.len() > 1 {
-                    split[1..].join(" ")
-                }
-                else {
-                    read_line("Name: ", false)
-                };
+    pub fn line_number(&self) -> usize {
+        match self {
+            Self::Stdin => 0,
+            Self::Script { next_line, .. } => *next_line
+        }
+    }
+
+    pub fn dry_run(&self) -> bool {
+        matches!(self, Self::Script { dry_run: true, .. })
+    }
+}
+
+fn run_command(session: &mut Session, command: &str, input: &mut Input) -> Result<(), Error> {
+
+    // only the command word ignores case, arguments such as file names are kept as typed
+    let command_word = command.split(' ').next().unwrap_or_default().to_lowercase();
+    let split: Vec<_> = std::iter::once(command_word.as_str()).chain(command.split(' ').skip(1)).collect();
+
+    match split[0] {
+        "section" => {
+
+            if split.len() == 1 {
+
+                for (idx, section) in session.event.sections.iter().enumerate() {
+
+                    let tournament = &section.tournament;
+                    let current_marker = if idx == session.section_idx { '*' } else { ' ' };
 
-                if !tournament.remove_player(&name) {
-                    println!("Error: could not find player \"{name}\".");
+                    println!(
+                        "{current_marker} {}: {} players, {} rounds, {}",
+                        section.description(),
+                        tournament.players.len(),
+                        tournament.rounds.len(),
+                        tournament.system.name()
+                    );
                 }
+
+                return Ok(());
+
             }
-            "standings" => {
 
-                let mut player_ids = tournament.get_all_player_ids().0;
+            if split[1].eq_ignore_ascii_case("new") {
 
-                let scores: Vec<_> =
-                    player_ids.iter()
-                        .cloned()
-                        .map(|id| tournament.calc_score(id))
-                        .collect();
+                let name = input.read_line("Section name: ", false)?;
 
-                let sb_scores: Vec<_> =
-                    player_ids.iter()
-                        .map(|player| tournament.calc_sonneborn_berger_score(*player, &scores))
-                        .collect();
+                let min_rating = read_rating_limit(input, "Minimum rating (leave blank for none): ")?;
+                let max_rating = read_rating_limit(input, "Maximum rating (leave blank for none): ")?;
 
-                player_ids.sort_by(|&id1, &id2| {
+                session.section_idx = session.event.add_section(name, min_rating, max_rating)?;
 
-                    if scores[id1] != scores[id2] {
-                        scores[id2].total_cmp(&scores[id1])
-                    }
-                    else if sb_scores[id1] != sb_scores[id2] {
-                        sb_scores[id2].total_cmp(&sb_scores[id1])
-                    }
-                    else {
-                        0.cmp(&0) // fix
-                    }
-                });
+                let board_rules = &mut session.event.sections[session.section_idx].tournament.board_rules;
+                let first_board = input.read_line(&format!("First board (leave blank for {}): ", board_rules.first_board()), true)?;
 
-                let mut stats: Vec<_> =
-                    player_ids.iter()
-                        .map(|_| (0, 0, 0, 0))
-                        .collect();
+                if !first_board.is_empty() {
+                    board_rules.first_board = Some(cli::parse_first_board(&first_board)?);
+                }
 
-                for round in tournament.rounds.iter() {
+            }
+            else {
+                session.section_idx = session.event.section_index(&split[1..].join(" "))?;
+            }
+        }
+        "add" => {
 
-                    if let Some(bye_player) = round.bye_player {
-                        stats[bye_player].3 += 1;
-                    }
-                    
-                    for game in round.games.iter() {
-                        match game.result {
-                            GameResult::Win => {
-                                stats[game.white_player].0 += 1;
-                                stats[game.black_player].2 += 1;
-                            }
-                            GameResult::Draw => {
-                                stats[game.white_player].1 += 1;
-                                stats[game.black_player].1 += 1;
-                            }
-                            GameResult::Loss => {
-                                stats[game.white_player].2 += 1;
-                                stats[game.black_player].0 += 1;
-                            }
-                            _ => {}
+            let name = input.read_line("Name: ", false)?;
+            let rating = input.read_line("Rating (leave blank for unknown): ", true)?.parse().ok();
+
+            session.event.add_player(session.section_idx, name, rating)?;
+
+        }
+        "start" => {
+
+            let used_system = match session.event.pair_next_round(session.section_idx) {
+                Ok(used_system) => used_system,
+                Err(Error::GamesPending(board_numbers)) => {
+
+                    let tournament = session.tournament();
+
+                    println!("The following games are still ongoing, report a score for them:");
+
+                    for game in tournament.rounds.last().unwrap().games.iter() {
+                        if board_numbers.contains(&game.board_number) {
+                            game.print(&tournament.players);
                         }
                     }
+
+                    return Err(Error::GamesPending(board_numbers));
+
                 }
+                Err(err) => return Err(err)
+            };
+
+            let tournament = session.tournament();
+
+            if used_system != tournament.system {
+                println!("Warning: Was not able to find {} pairings, settling with {} pairings (potential repeats/bad matches).", tournament.system.name(), used_system.name());
+            }
 
-                let mut prev_score = f32::MIN;
-                let mut prev_sb_score = f32::MIN;
-                let mut placing = 0;
+            print!("{}", cli::pairings_output(tournament, Format::Text));
 
-                println!("====Round {} Standings====", tournament.rounds.len());
-                println!("## | Score | SB Score | W/D/L/B | Name");
-                println!("---|-------|----------|---------|------------");
+        }
+        "report" => {
 
-                for (idx, &id) in player_ids.iter().enumerate() {
+            if session.event.sections.iter().all(|section| !section.tournament.started()) {
+                return Err(Error::TournamentNotStarted);
+            }
 
-                    let score = scores[id];
-                    let sb_score = sb_scores[id];
-                    let (wins, draws, losses, byes) = stats[id];
+            // `report --round <n> ...` corrects a result of an earlier round
+            let (round_idx, split) = match split[..] {
+                [command, "--round", round, ref rest @ ..] => {
 
-                    if score != prev_score || sb_score != prev_sb_score {
-                        placing = idx + 1;
-                        prev_score = score;
-                        prev_sb_score = sb_score;
-                    }
+                    let round_idx = round.parse::<usize>()
+                        .ok()
+                        .and_then(|round_number| round_number.checked_sub(1))
+                        .ok_or_else(|| Error::Parse(format!("Invalid round \"{round}\"")))?;
 
-                    let withdraw_star = if tournament.players[id].active {
-                        ' '
-                    }
-                    else {
-                        '*'
-                    };
+                    (Some(round_idx), [&[command], rest].concat())
 
-                    println!("{: >2} | {score: >5.1}{withdraw_star}| {sb_score: >8.2} | {wins}/{draws}/{losses}/{byes} | {}", placing, tournament.players[id].name);
                 }
+                _ => (None, split.clone())
+            };
+
+            // `report 1:1-0 2:½ 3:0-1` reports several boards of the current round at once
+            if round_idx.is_none() && split.len() > 1 && split[1].contains(':') {
+
+                let results = parse_board_results(&split[1..].join(" "))?;
+                session.event.report_results(&results, session.section_idx)?;
+
+                println!("{}", pending_boards_message(&session.event.pending_boards()));
+                return Ok(());
+
             }
-            "start" => {
 
-                if let Some(prev_round) = tournament.rounds.last() {
+            let number_text = if split.len() > 1 {
+                split[1].to_string()
+            }
+            else {
+                input.read_line("Board number: ", true)?
+            };
 
-                    let mut active_games = Vec::new();
+            let Ok(board_number) = number_text.parse::<u32>()
+            else {
+                return Err(Error::Parse(format!("Invalid board number \"{number_text}\"")));
+            };
 
-                    for (idx, game) in prev_round.games.iter().enumerate() {
-                        if game.result == GameResult::Pending {
-                            active_games.push(idx);
-                        }
-                    }
+            let section_idx = match round_idx {
+                Some(round_idx) => session.event.find_board_in_round(round_idx, board_number, session.section_idx)?,
+                None => session.event.find_board(board_number, session.section_idx)?
+            };
 
-                    if !active_games.is_empty() {
+            let tournament = &mut session.event.sections[section_idx].tournament;
 
-                        println!("Error: The following games are still ongoing, report a score for them:");
-    
-                        for game_idx in active_games {
-                            prev_round.games[game_idx].print(&tournament.players);
-                        }
-    
-                        continue;
+            let white_player = round_idx.map_or_else(|| tournament.current_round(), |round_idx| tournament.rounds.get(round_idx))
+                .and_then(|round| round.games.iter().find(|game| game.board_number == board_number))
+                .map(|game| game.white_player)
+                .ok_or(Error::UnknownBoard(board_number))?;
+
+            let result_string = if split.len() > 2 {
+                split[2..].join(" ")
+            }
+            else {
+                input.read_line(&format!("Result for white player ({}) [W]in/[D]raw/[L]oss/[U]nreport, or 1-0, ½-½, 0-1, +/-, -/+, 0-0: ", tournament.players[white_player].name), true)?
+            };
 
+            let result = GameResult::parse(&result_string)?;
+
+            match round_idx {
+                Some(round_idx) => {
+
+                    let affected = tournament.correct_result(round_idx, board_number, result, &read_arbiter(input)?)?;
+
+                    for warning in text::correction_warnings(&affected, &tournament.players) {
+                        println!("Warning: {warning}.");
                     }
                 }
-
-                let mut pairing_result = if !tournament.started() {
-                    Round::from_seeding(&tournament)
+                None => {
+                    tournament.report_result(board_number, result)?;
+                    println!("{}", pending_boards_message(&session.event.pending_boards()));
                 }
-                else {
-                    Round::generate_dutch(&tournament)
-                };
+            }
+        }
+        "save" => {
 
-                let scores = tournament.get_player_scores();
-                pairing_result.games.sort_by(|game1, game2| {
+            let filename = if split.len() > 1 {
+                split[1..].join(" ")
+            }
+            else {
+                input.read_line("Filename: ", false)?
+            };
 
-                    let game1_score = scores[game1.white_player] + scores[game1.black_player];
-                    let game2_score = scores[game2.white_player] + scores[game2.black_player];
-                
-                    game2_score.total_cmp(&game1_score)
-                
-                });
+            if input.dry_run() {
+                println!("Skipping save (dry run).");
+                return Ok(());
+            }
 
-                for (idx, game) in pairing_result.games.iter_mut().enumerate() {
-                    game.board_number = idx as u32 + 1;
-                }
+            session.event.save(&filename)?;
 
-                println!("====Round {} Pairings====", tournament.rounds.len() + 1);
-                println!("[Board #] White vs Black");
-                println!("-------------------------------------");
+        }
+        "load" => {
 
-                for pairing in pairing_result.games.iter() {
-                    pairing.print(&tournament.players);
-                }
+            let filename = if split.len() > 1 {
+                split[1..].join(" ")
+            }
+            else {
+                input.read_line("Filename: ", false)?
+            };
+
+            session.event = Event::load(&filename)?;
+            session.section_idx = 0;
+
+        }
+        "name" => {
+
+            let name = if split.len() > 1 {
+                split[1..].join(" ")
+            }
+            else {
+                input.read_line("Tournament name: ", false)?
+            };
+
+            // a single section is the whole event
+            if session.event.sections.len() == 1 {
+                session.event.name = name.clone();
+            }
+
+            session.tournament().name = name;
+
+        }
+        "html" => {
+
+            let filename = if split.len() > 1 {
+                split[1..].join(" ")
+            }
+            else {
+                input.read_line("Filename: ", false)?
+            };
+
+            if input.dry_run() {
+                println!("Skipping HTML export (dry run).");
+                return Ok(());
+            }
+
+            if session.event.sections.len() > 1 {
+                std::fs::write(&filename, session.event.to_html()).map_err(Error::file(&filename))?;
+            }
+            else {
+                std::fs::write(&filename, session.tournament().to_html()).map_err(Error::file(&filename))?;
+            }
+        }
+        "run" => {
+
+            let Some(filename) = split.get(1)
+            else {
+                return Err(Error::InvalidCommand("Usage: run <script> [--dry-run] [--keep-going]".to_string()));
+            };
+
+            let options = batch::BatchOptions {
+                dry_run: split.contains(&"--dry-run") || input.dry_run(),
+                keep_going: split.contains(&"--keep-going")
+            };
+
+            let script = std::fs::read_to_string(filename).map_err(Error::file(filename))?;
+            let failures = batch::run_script(session, &script, &options);
+
+            if failures > 0 {
+                return Err(Error::InvalidCommand(format!("{failures} commands in {filename} failed")));
+            }
+        }
+        #[cfg(feature = "tui")]
+        "tui" => tui::run(&mut session.event, session.section_idx, None)?,
+        _ => {
+
+            let old_num_rounds = session.tournament().rounds.len();
+            let old_round = session.tournament().current_round().cloned();
+
+            run_section_command(session.tournament(), &split, command, input)?;
+
+            let tournament = session.tournament();
+            let num_rounds = tournament.rounds.len();
+
+            let round_changed = match (old_round, tournament.current_round()) {
+                _ if num_rounds < old_num_rounds => false,
+                (Some(old_round), Some(round)) => {
+
+                    let board_numbers = |round: &Round| round.games.iter().map(|game| game.board_number).collect::<Vec<_>>();
+
+                    num_rounds > old_num_rounds || !round.same_pairings(&old_round) || board_numbers(round) != board_numbers(&old_round)
 
-                if let Some(bye_player) = pairing_result.bye_player {
-                    println!("Bye: {}", tournament.players[bye_player].name)
                 }
+                (None, round) => round.is_some(),
+                (Some(_), None) => false
+            };
+
+            // boards are shared between sections, so a new or changed round is numbered across the event
+            if round_changed {
+                session.event.number_boards(session.section_idx, num_rounds - 1);
+            }
+        }
+    }
+
+    Ok(())
+
+}
+
+fn read_rating_limit(input: &mut Input, prompt: &str) -> Result<Option<u32>, Error> {
+
+    let text = input.read_line(prompt, true)?;
+
+    if text.is_empty() {
+        return Ok(None);
+    }
+
+    text.parse()
+        .map(Some)
+        .map_err(|_| Error::Parse(format!("Invalid elo \"{text}\"")))
+
+}
+
+// finds a player by pairing number or name, asking which one is meant when several match
+// the login name, asked for when there is none
+fn read_arbiter(input: &mut Input) -> Result<String, Error> {
+    match cli::default_arbiter() {
+        Some(arbiter) => Ok(arbiter),
+        None => input.read_line("Your name for the audit log: ", false)
+    }
+}
+
+fn choose_player(tournament: &Tournament, input: &mut Input, query: &str) -> Result<PlayerID, Error> {
+
+    let matches = tournament.find_players(query);
+
+    if matches.len() < 2 {
+        return tournament.find_player(query);
+    }
+
+    println!("\"{}\" could be any of:", query.trim());
+
+    for (idx, &player) in matches.iter().enumerate() {
+
+        let rating = tournament.players[player].rating.map(|rating| format!(" ({rating})")).unwrap_or_default();
+
+        println!("({}) {}{rating}", idx + 1, tournament.players[player].label());
+
+    }
+
+    let choice = input.read_line("Which one? ", true)?;
+
+    choice.parse::<usize>()
+        .ok()
+        .and_then(|choice| matches.get(choice.checked_sub(1)?))
+        .copied()
+        .ok_or_else(|| Error::Parse(format!("Invalid choice \"{choice}\"")))
 
-                tournament.rounds.push(pairing_result);
+}
+
+fn read_player(tournament: &Tournament, input: &mut Input, prompt: &str) -> Result<PlayerID, Error> {
+    let query = input.read_line(prompt, false)?;
+    choose_player(tournament, input, &query)
+}
 
+fn run_section_command(tournament: &mut Tournament, split: &[&str], command: &str, input: &mut Input) -> Result<(), Error> {
+
+    match split[0] {
+        "remove" => {
+
+            let name = if split.len() > 1 {
+                split[1..].join(" ")
             }
-            "reject" => {
-                if read_line("Are you sure you want to reject the current round? ", true).to_lowercase().chars().nth(0).unwrap_or('n') == 'y' {
-                    tournament.rounds.pop();
+            else {
+                input.read_line("Name or pairing number: ", false)?
+            };
+
+            let player = choose_player(tournament, input, &name)?;
+
+            // a partial or misspelt name has to be confirmed
+            if tournament.find_player_exactly(&name).ok() != Some(player) {
+
+                let answer = input.read_line(&format!("Withdraw {}? (y/n): ", tournament.players[player].label()), true)?;
+
+                if answer != "y" && answer != "yes" {
+                    println!("Cancelled.");
+                    return Ok(());
                 }
             }
-            "manual" => {
-                
-                let mut remaining_players = tournament.get_active_player_ids();
-                let mut round_pairings: Vec<Pairing> = Vec::new();
 
-                let mut add_round = true;
+            tournament.players[player].active = false;
+        }
+        "standings" => {
+
+            let year = tournament.year();
 
-                while remaining_players.0.len() > 1 {
+            match split.get(1) {
+                Some(all) if all.eq_ignore_ascii_case("all") => {
 
-                    println!("\nCurrent pairings:");
+                    print!("{}", cli::standings_output(tournament, Format::Text));
 
-                    for pairing in &round_pairings {
-                        println!("{} vs. {}", tournament.players[pairing.white_player].name, tournament.players[pairing.black_player].name);
+                    for &category in tournament.categories.iter() {
+                        print!("{}", cli::category_standings_output(tournament, category, year, Format::Text));
                     }
+                }
+                Some(name) => print!("{}", cli::category_standings_output(tournament, cli::parse_category(name)?, year, Format::Text)),
+                None => print!("{}", cli::standings_output(tournament, Format::Text))
+            }
+        }
+        "categories" => {
 
-                    println!("Remaining players:");
+            if split.len() > 1 {
+                tournament.categories = split[1..].iter()
+                    .map(|name| cli::parse_category(name))
+                    .collect::<Result<_, _>>()?;
+            }
 
-                    for (idx, &player) in remaining_players.0.iter().enumerate() {
-                        println!("({}) {}", idx, tournament.players[player].name);
-                    }
+            let names: Vec<_> = tournament.categories.iter()
+                .map(|category| category.name())
+                .collect();
 
-                    let response = read_line("Enter white player number ('cancel' to cancel): ", true);
+            println!("Categories: [{}]", names.join(", "));
 
-                    if response == "cancel" {
-                        add_round = false;
-                        break;
-                    }
+        }
+        "crosstable" => {
+            print!("{}", cli::crosstable_output(tournament, Format::Text));
+        }
+        "details" => {
 
-                    let Ok(player1_idx) = response.parse::<usize>()
-                    else {
-                        println!("Error: invalid index");
-                        continue;
-                    };
+            let player = read_player(tournament, input, "Name or pairing number: ")?;
+            let sex = input.read_line("Sex (m/f, leave blank for unknown): ", true)?;
+            let birth_year = input.read_line("Birth year (leave blank for unknown): ", true)?;
+            let title = input.read_line("Title (GM, IM, WGM, FM, WIM, CM, WFM, WCM, leave blank for none): ", true)?;
 
-                    if player1_idx >= remaining_players.0.len() {
-                        println!("Error: invalid index");
-                        continue;
-                    }
+            let player = &mut tournament.players[player];
 
-                    let Ok(player2_idx) = read_line("Enter black player number: ", true).parse::<usize>()
-                    else {
-                        println!("Error: invalid index");
-                        continue;
-                    };
+            player.sex = Sex::from_name(&sex);
+            player.birth_year = birth_year.parse().ok();
+            player.title = Title::from_name(&title);
 
-                    if player2_idx >= remaining_players.0.len() {
-                        println!("Error: invalid index");
-                        continue;
-                    }
+        }
+        "prizes" => {
 
-                    if player1_idx == player2_idx {
-                        println!("Error: indexes match");
-                        continue;
-                    }
+            let filename = if split.len() > 1 {
+                split[1..].join(" ")
+            }
+            else {
+                input.read_line("Prize fund file: ", false)?
+            };
 
-                    round_pairings.push(Pairing::new(remaining_players.0[player1_idx], remaining_players.0[player2_idx]));
+            let fund = PrizeFund::parse(&std::fs::read_to_string(&filename).map_err(Error::file(&filename))?)?;
+            print!("{}", prize::payout_list(&fund.distribute(tournament, tournament.year()), &tournament.players));
+
+        }
+        "rounds" => {
 
-                    remaining_players.0.remove(player1_idx.max(player2_idx));
-                    remaining_players.0.remove(player1_idx.min(player2_idx));
+            if split.len() > 1 {
+                tournament.total_rounds = Some(cli::parse_total_rounds(split[1])?);
+            }
+
+            print!("{}", tournament.rounds_text());
+
+        }
+        "boards" => print!("{}", tournament.board_rules.as_string(&tournament.players)),
+        "board" => {
+
+            let player = read_player(tournament, input, "Name or pairing number: ")?;
+            let rule = input.read_line("Board number, 'top' for the top boards or 'clear': ", true)?;
+
+            cli::set_board_rule(tournament, player, &rule)?;
+
+        }
+        "finish" => tournament.finish()?,
+        "reopen" => tournament.reopen(&read_arbiter(input)?)?,
+        "contention" => {
+
+            let numbers: Vec<_> = split[1..split.len().min(3)].iter()
+                .map(|text| text.parse::<usize>())
+                .collect();
+
+            let usage = || Error::InvalidCommand("Usage: contention [rounds remaining] [places]".to_string());
+
+            let (rounds_remaining, places) = match numbers[..] {
+                [] => (tournament.rounds_remaining().ok_or_else(usage)?, 1),
+                [Ok(rounds_remaining)] => (rounds_remaining, 1),
+                [Ok(rounds_remaining), Ok(places)] if places > 0 => (rounds_remaining, places),
+                _ => return Err(usage())
+            };
+
+            print!("{}", tournament.contention(places, rounds_remaining).as_string(&tournament.players));
+
+        }
+        "forecast" => {
+
+            let numbers: Vec<_> = split[1..split.len().min(3)].iter()
+                .map(|text| text.parse::<usize>())
+                .collect();
+
+            let usage = || Error::InvalidCommand("Usage: forecast [rounds remaining] [simulations]".to_string());
+
+            let (rounds_remaining, num_simulations) = match numbers[..] {
+                [] => (tournament.rounds_remaining().ok_or_else(usage)?, cli::DEFAULT_SIMULATIONS),
+                [Ok(rounds_remaining)] => (rounds_remaining, cli::DEFAULT_SIMULATIONS),
+                [Ok(rounds_remaining), Ok(num_simulations)] if num_simulations > 0 => (rounds_remaining, num_simulations),
+                _ => return Err(usage())
+            };
+
+            let forecast = tournament.forecast(rounds_remaining, num_simulations, None, tournament.year(), &mut rand::thread_rng())?;
+            print!("{}", forecast.as_string(&tournament.players));
+
+        }
+        "system" => {
+
+            let name = if split.len() > 1 {
+                split[1].to_string()
+            }
+            else {
+                input.read_line("Pairing system (dutch/monrad/danish): ", true)?
+            };
 
+            let Some(system) = PairingSystem::from_name(&name)
+            else {
+                return Err(Error::Parse(format!("Unknown pairing system \"{name}\"")));
+            };
+
+            tournament.system = system;
+
+        }
+        "reject" => {
+
+            if tournament.finished {
+                return Err(Error::TournamentFinished);
+            }
+
+            if input.read_line("Are you sure you want to reject the current round? ", true)?.to_lowercase().chars().next().unwrap_or('n') == 'y' {
+                tournament.rounds.pop();
+            }
+        }
+        "manual" => {
+
+            if tournament.finished {
+                return Err(Error::TournamentFinished);
+            }
+
+            if let Some(total_rounds) = tournament.total_rounds.filter(|_| tournament.rounds_remaining() == Some(0)) {
+                return Err(Error::AllRoundsPaired(total_rounds));
+            }
+
+            let mut remaining_players = tournament.get_active_player_ids();
+            let mut round_pairings: Vec<Pairing> = Vec::new();
+
+            let mut add_round = true;
+
+            while remaining_players.0.len() > 1 {
+
+                println!("\nCurrent pairings:");
+
+                for pairing in &round_pairings {
+                    println!("{} vs. {}", tournament.players[pairing.white_player].label(), tournament.players[pairing.black_player].label());
                 }
 
-                if add_round {
-                    
-                    let mut round = Round::from_pairings(round_pairings, remaining_players.0.pop());
-                    
-                    for board_num in 1..=round.games.len() {
-                        round.games[board_num - 1].board_number = board_num as u32;
-                    }
+                println!("Remaining players:");
+
+                for (idx, &player) in remaining_players.0.iter().enumerate() {
+                    println!("({}) {}", idx, tournament.players[player].label());
+                }
 
-                    tournament.rounds.push(round);
+                let response = input.read_line("Enter white player number ('cancel' to cancel): ", true)?;
 
+                if response == "cancel" {
+                    add_round = false;
+                    break;
                 }
-            }
-            "report" => {
 
-                let Some(round) = tournament.rounds.last_mut() else {
-                    println!("Error: Tournament has not started.");
+                let Ok(player1_idx) = response.parse::<usize>()
+                else {
+                    println!("Error: invalid index");
                     continue;
                 };
 
-                let number_text = if split.len() > 1 {
-                    split[1].to_string()
+                if player1_idx >= remaining_players.0.len() {
+                    println!("Error: invalid index");
+                    continue;
                 }
-                else {
-                    read_line("Board number: ", true)
-                };
 
-                let Ok(board_number) = number_text.parse::<u32>()
+                let Ok(player2_idx) = input.read_line("Enter black player number: ", true)?.parse::<usize>()
                 else {
-                    println!("Error: Invalid board number.");
+                    println!("Error: invalid index");
                     continue;
                 };
 
-                let mut found_game = false;
-
-                for game in round.games.iter_mut() {
-                    if board_number == game.board_number {
-
-                        let result_string = if split.len() > 2 {
-                            split[2].to_string()
-                        }
-                        else {
-                            read_line(&format!("Result for white player ({}) [W]in/[D]raw/[L]oss/[U]nreport: ", tournament.players[game.white_player].name), true)
-                        };
-                    
-                        game.result = match result_string.to_lowercase().chars().next().unwrap() {
-                            'w' => GameResult::Win,
-                            'd' => GameResult::Draw,
-                            'l' => GameResult::Loss,
-                            'u' => GameResult::Pending,
-                            _ => {
-                                println!("Error: Invalid match result.");
-                                break;
-                            }
-                        };
-                    
-                        found_game = true;
-                        break;
-                    
-                    }
+                if player2_idx >= remaining_players.0.len() {
+                    println!("Error: invalid index");
+                    continue;
                 }
 
-                if !found_game {
-                    println!("Error: No active game at board {board_number}.");
+                if player1_idx == player2_idx {
+                    println!("Error: indexes match");
+                    continue;
                 }
+
+                round_pairings.push(Pairing::new(remaining_players.0[player1_idx], remaining_players.0[player2_idx]));
+
+                remaining_players.0.remove(player1_idx.max(player2_idx));
+                remaining_players.0.remove(player1_idx.min(player2_idx));
+
+            }
+
+            if add_round {
+                
+                let round = Round::from_pairings(round_pairings, remaining_players.0.pop());
+
+                print_violations(&round.check(tournament, Some(PairingSystem::Dutch)), &tournament.players);
+
+                tournament.start();
+                tournament.rounds.push(round);
+                tournament.number_boards(tournament.rounds.len() - 1, tournament.board_rules.first_board(), &[]);
+
+            }
+        }
+        "corrections" => {
+            for entry in tournament.audit_log() {
+                println!("{entry}");
+            }
+        }
+        "swap" => {
+
+            let player1 = read_player(tournament, input, "First player: ")?;
+            let player2 = read_player(tournament, input, "Second player: ")?;
+
+            print_violations(&tournament.swap_players(player1, player2)?, &tournament.players);
+
+        }
+        "bye" => {
+
+            let player = read_player(tournament, input, "Give the bye to: ")?;
+            print_violations(&tournament.move_bye(player)?, &tournament.players);
+
+        }
+        "colours" => {
+
+            let number_text = if split.len() > 1 {
+                split[1].to_string()
             }
-            "games" => {
+            else {
+                input.read_line("Board number: ", true)?
+            };
+
+            let Ok(board_number) = number_text.parse::<u32>()
+            else {
+                return Err(Error::Parse(format!("Invalid board number \"{number_text}\"")));
+            };
+
+            print_violations(&tournament.swap_colours(board_number)?, &tournament.players);
+
+        }
+        "check" => {
 
-                let Some(round) = tournament.rounds.last()
+            let system = if split.len() > 1 {
+
+                let Some(system) = PairingSystem::from_name(split[1])
                 else {
-                    println!("Error: Tournament has not started.");
-                    continue;    
+                    return Err(Error::Parse(format!("Unknown pairing system \"{}\"", split[1])));
                 };
 
+                system
+
+            }
+            else {
+                PairingSystem::Dutch
+            };
+
+            let Some(round) = tournament.rounds.pop()
+            else {
+                return Err(Error::TournamentNotStarted);
+            };
+
+            print_violations(&round.check(tournament, Some(system)), &tournament.players);
+            tournament.rounds.push(round);
+
+        }
+        "games" => {
+
+            let Some(round) = tournament.rounds.last()
+            else {
+                return Err(Error::TournamentNotStarted);
+            };
+
+            if split.get(1).is_some_and(|order| "alphabetical".starts_with(&order.to_lowercase())) {
+                print!("{}", cli::alphabetical_output(tournament, Format::Text));
+                return Ok(());
+            }
+
+            for game in round.games.iter() {
+                game.print(&tournament.players);
+            }
+        }
+        "history" => {
+
+            let name = if split.len() > 1 {
+                split[1..].join(" ")
+            }
+            else {
+                input.read_line("Name or pairing number: ", true)?
+            };
+
+            print!("{}", cli::history_output(tournament, choose_player(tournament, input, &name)?, Format::Text));
+
+        }
+        "print" => {
+
+            let Some(document) = tournament.rounds.len().checked_sub(1).and_then(|round_idx| tournament.print_html(round_idx))
+            else {
+                return Err(Error::TournamentNotStarted);
+            };
+
+            let filename = if split.len() > 1 {
+                split[1..].join(" ")
+            }
+            else {
+                input.read_line("Filename: ", false)?
+            };
+
+            if input.dry_run() {
+                println!("Skipping print export (dry run).");
+                return Ok(());
+            }
+
+            std::fs::write(&filename, document).map_err(Error::file(&filename))?;
+
+        }
+        "pgn" => {
+
+            let Some(round_pgn) = tournament.rounds.len().checked_sub(1).and_then(|round_idx| tournament.round_pgn(round_idx))
+            else {
+                return Err(Error::TournamentNotStarted);
+            };
+
+            let filename = if split.len() > 1 {
+                split[1..].join(" ")
+            }
+            else {
+                input.read_line("Filename: ", false)?
+            };
+
+            if input.dry_run() {
+                println!("Skipping PGN export (dry run).");
+                return Ok(());
+            }
+
+            std::fs::write(&filename, round_pgn).map_err(Error::file(&filename))?;
+
+        }
+        "import" => {
+
+            let filename = if split.len() > 1 {
+                split[1..].join(" ")
+            }
+            else {
+                input.read_line("Filename: ", false)?
+            };
+
+            for import in tournament.import_pgn(&std::fs::read_to_string(&filename).map_err(Error::file(&filename))?)? {
+                println!("{}", import.as_string());
+            }
+        }
+        "export" => {
+
+            let filename = if split.len() > 1 {
+                split[1..].join(" ")
+            }
+            else {
+                input.read_line("Filename: ", false)?
+            };
+
+            if input.dry_run() {
+                println!("Skipping export (dry run).");
+                return Ok(());
+            }
+
+            let mut file = File::options()
+                .write(true)
+                .create_new(true)
+                .open(&filename)
+                .map_err(Error::file(&filename))?;
+            
+            for (idx, round) in tournament.rounds.iter().enumerate() {
+
+                let _ = file.write(format!("\nRound {}\n", idx + 1).as_bytes());
+
                 for game in round.games.iter() {
-                    game.print(&tournament.players);
+                    let _ = file.write(game.as_string(&tournament.players).as_bytes());
+                    let _ = file.write("\n".as_bytes());
                 }
             }
-            "export" => {
+            
+        }
+        "list" => {
+            println!("Commands: [section, add, remove, standings, categories, crosstable, details, prizes, rounds, boards, board, finish, reopen, contention, forecast, start, reject, manual, swap, bye, colours, system, check, report, corrections, games, history, save, load, name, export, html, print, pgn, import, run, tui, rtg, list]");
+        }
+        "rtg" => {
+
+            let numbers: Vec<_> = split[1..split.len().min(4)].iter()
+                .map(|text| text.parse::<usize>())
+                .collect();
+
+            let [Ok(num_tournaments), Ok(num_players), Ok(num_rounds)] = numbers[..]
+            else {
+                return Err(Error::InvalidCommand("Usage: rtg <tournaments> <players> <rounds> [system] [output directory]".to_string()));
+            };
+
+            let system = match split.get(4) {
+                Some(name) => {
+
+                    let Some(system) = PairingSystem::from_name(name)
+                    else {
+                        return Err(Error::Parse(format!("Unknown pairing system \"{name}\"")));
+                    };
+
+                    system
 
-                let filename = if split.len() > 1 {
-                    split[1..].join(" ")
                 }
-                else {
-                    read_line("Filename: ", true)
-                };
+                None => PairingSystem::Dutch
+            };
 
-                let Ok(mut file) = File::options()
-                    .write(true)
-                    .create_new(true)
-                    .open(filename)
-                else {
-                    println!("Error: File already exists");
-                    continue;
-                };
-                
-                for (idx, round) in tournament.rounds.iter().enumerate() {
+            let report = rtg::StressTestReport::run(num_tournaments, num_players, num_rounds, system, rand::random());
+            println!("{}", report.as_string());
 
-                    let _ = file.write(format!("\nRound {}\n", idx + 1).as_bytes());
+            if let Some(directory) = split.get(5).filter(|_| !input.dry_run()) {
+                for (seed, random_tournament) in report.tournaments.iter() {
 
-                    for game in round.games.iter() {
-                        let _ = file.write(game.as_string(&tournament.players).as_bytes());
-                        let _ = file.write("\n".as_bytes());
+                    let path = std::path::Path::new(directory).join(format!("rtg-{seed}.trf"));
+
+                    if std::fs::write(&path, random_tournament.tournament.to_trf()).is_err() {
+                        return Err(Error::InvalidCommand(format!("Could not write {}", path.display())));
                     }
                 }
-                
             }
-            "list" => {
-                println!("Commands: [add, remove, standings, start, reject, manual, round, games, export, list]");
+        }
+        // testing only
+        "sweep" => {
+
+            if tournament.finished {
+                return Err(Error::TournamentFinished);
             }
-            // testing only
-            "sweep" => {
 
-                let Some(round) = tournament.rounds.last_mut() else {
-                    println!("Error: Tournament has not started.");
-                    continue;
-                };
+            let Some(round) = tournament.rounds.last_mut() else {
+                return Err(Error::TournamentNotStarted);
+            };
 
-                for game in round.games.iter_mut() {
-                    game.result = GameResult::Win;
-                }
+            for game in round.games.iter_mut() {
+                game.result = GameResult::Win;
             }
-            _ => println!("Unknown command: {}", command)
         }
+        _ => return Err(Error::InvalidCommand(format!("Unknown command \"{command}\"")))
+    }
+
+    Ok(())
+
+}
+
+fn print_violations(violations: &[Violation], players: &[Player]) {
+
+    if violations.is_empty() {
+        println!("Round follows all pairing rules.");
+        return;
+    }
+
+    println!("Warning: Round breaks the following pairing rules:");
+
+    for violation in violations {
+        println!("- {}", violation.as_string(players));
     }
 }
@@ -0,0 +1,281 @@
+use crate::*;
+
+// the typos allowed in a search of this many characters, none in short ones which are too easily close to any name
+fn max_typos(length: usize) -> usize {
+    match length {
+        0..=3 => 0,
+        4..=6 => 1,
+        _ => 2
+    }
+}
+
+impl Tournament {
+
+    /// All players by rating (unrated last), then title (untitled last), then name.
+    pub fn get_starting_order(&self) -> Vec<PlayerID> {
+
+        let mut player_ids = self.get_all_player_ids().0;
+
+        player_ids.sort_by(|&id1, &id2| {
+
+            let (player1, player2) = (&self.players[id1], &self.players[id2]);
+
+            player2.rating.cmp(&player1.rating)
+                .then(player1.title.is_none().cmp(&player2.title.is_none()))
+                .then(player1.title.cmp(&player2.title))
+                .then(player1.name.to_lowercase().cmp(&player2.name.to_lowercase()))
+
+        });
+
+        player_ids
+
+    }
+
+    /// Every player's pairing number, players without one following on in starting order as if numbered now.
+    pub fn get_pairing_numbers(&self) -> Vec<u32> {
+
+        let mut numbers: Vec<_> = self.players.iter()
+            .map(|player| player.pairing_number)
+            .collect();
+
+        let mut next_number = numbers.iter().flatten().max().unwrap_or(&0) + 1;
+
+        for id in self.get_starting_order() {
+            if numbers[id].is_none() {
+                numbers[id] = Some(next_number);
+                next_number += 1;
+            }
+        }
+
+        numbers.into_iter().flatten().collect()
+
+    }
+
+    /// Gives every player without a pairing number the next free one, in starting order.
+    /// Called when the tournament starts, after which late entries are numbered as they arrive.
+    pub fn assign_pairing_numbers(&mut self) {
+
+        let numbers = self.get_pairing_numbers();
+
+        for (player, number) in self.players.iter_mut().zip(numbers) {
+            player.pairing_number = Some(number);
+        }
+    }
+
+    /// Finds a player by pairing number (`3` or `#3`) or by name, see [`Tournament::find_players`].
+    /// Several equally good matches give [`Error::AmbiguousPlayer`].
+    pub fn find_player(&self, query: &str) -> Result<PlayerID, Error> {
+
+        self.single_match(query.trim(), self.find_players(query))
+    }
+
+    /// Finds a player by pairing number or by their full name ignoring case, for when a guess could do harm.
+    pub fn find_player_exactly(&self, query: &str) -> Result<PlayerID, Error> {
+
+        let query = query.trim();
+
+        let matches = match query.trim_start_matches('#').parse::<u32>() {
+            Ok(number) => self.matching_players(|player| player.pairing_number == Some(number)),
+            Err(_) => self.matching_players(|player| player.name.to_lowercase() == query.to_lowercase())
+        };
+
+        self.single_match(query, matches)
+
+    }
+
+    /// The players best matching a pairing number or name. Names are matched ignoring case,
+    /// then by the words they contain (`carl` or `m carl` for Magnus Carlsen), then allowing a typo in searches
+    /// of four or more letters and two in searches of seven or more.
+    pub fn find_players(&self, query: &str) -> Vec<PlayerID> {
+
+        let query = query.trim();
+
+        if let Ok(number) = query.trim_start_matches('#').parse::<u32>() {
+            return self.matching_players(|player| player.pairing_number == Some(number));
+        }
+
+        let query = query.to_lowercase();
+        let words: Vec<_> = query.split_whitespace().collect();
+
+        if words.is_empty() {
+            return Vec::new();
+        }
+
+        let exact = self.matching_players(|player| player.name.to_lowercase() == query);
+
+        if !exact.is_empty() {
+            return exact;
+        }
+
+        let by_words = self.matching_players(|player| {
+            let name = player.name.to_lowercase();
+            let name_words: Vec<_> = name.split_whitespace().collect();
+            words.iter().all(|word| name_words.iter().any(|name_word| name_word.contains(word)))
+        });
+
+        if !by_words.is_empty() {
+            return by_words;
+        }
+
+        // typos are counted in the whole name, or word by word for part of a name
+        let whole_limit = max_typos(query.chars().count());
+
+        let typos: Vec<_> = self.players.iter()
+            .map(|player| {
+
+                let name = player.name.to_lowercase();
+
+                let whole = Some(edit_distance(&name, &query)).filter(|&typos| typos <= whole_limit);
+
+                // each word only gets the typos allowed for its own length
+                let by_words = words.iter()
+                    .map(|word| {
+                        name.split_whitespace()
+                            .map(|name_word| edit_distance(name_word, word))
+                            .min()
+                            .filter(|&typos| typos <= max_typos(word.chars().count()))
+                    })
+                    .sum::<Option<usize>>();
+
+                whole.into_iter().chain(by_words).min()
+
+            })
+            .collect();
+
+        match typos.iter().flatten().min() {
+            Some(&min_typos) => (0..self.players.len())
+                .filter(|&id| typos[id] == Some(min_typos))
+                .collect(),
+            None => Vec::new()
+        }
+    }
+
+    fn single_match(&self, query: &str, matches: Vec<PlayerID>) -> Result<PlayerID, Error> {
+        match matches[..] {
+            [] => Err(Error::UnknownPlayer(query.to_string())),
+            [player] => Ok(player),
+            _ => Err(Error::AmbiguousPlayer(
+                query.to_string(),
+                matches.into_iter().map(|id| self.players[id].label()).collect()
+            ))
+        }
+    }
+
+    fn matching_players(&self, matches: impl Fn(&Player) -> bool) -> Vec<PlayerID> {
+        (0..self.players.len())
+            .filter(|&id| matches(&self.players[id]))
+            .collect()
+    }
+}
+
+// the number of single character insertions, deletions or substitutions turning one string into the other
+fn edit_distance(text1: &str, text2: &str) -> usize {
+
+    let chars2: Vec<_> = text2.chars().collect();
+    let mut prev_row: Vec<_> = (0..=chars2.len()).collect();
+
+    for (idx1, char1) in text1.chars().enumerate() {
+
+        let mut row = vec![idx1 + 1];
+
+        for (idx2, &char2) in chars2.iter().enumerate() {
+
+            let substitution = prev_row[idx2] + (char1 != char2) as usize;
+            row.push(substitution.min(prev_row[idx2 + 1] + 1).min(row[idx2] + 1));
+
+        }
+
+        prev_row = row;
+
+    }
+
+    prev_row[chars2.len()]
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    fn tournament(names: &[(&str, Option<u32>)]) -> Tournament {
+
+        let mut tournament = Tournament::new();
+
+        for &(name, rating) in names {
+            tournament.add_player(name.to_string(), rating);
+        }
+
+        tournament
+
+    }
+
+    #[test]
+    fn numbers_follow_rating_then_title_then_name() {
+
+        let mut tournament = tournament(&[("Dee", None), ("Cid", Some(1800)), ("Bob", Some(1800)), ("Ann", Some(1800)), ("Eve", Some(2000))]);
+        tournament.players[1].title = Some(Title::FM);
+
+        tournament.assign_pairing_numbers();
+
+        let numbers: Vec<_> = tournament.players.iter().map(|player| player.pairing_number.unwrap()).collect();
+        assert_eq!(numbers, [5, 2, 4, 3, 1]);
+
+        tournament.add_player("Fay".to_string(), Some(2500));
+        tournament.assign_pairing_numbers();
+        assert_eq!(tournament.players[5].pairing_number, Some(6));
+
+    }
+
+    #[test]
+    fn find_player_by_number_name_and_part_of_name() {
+
+        let mut tournament = tournament(&[("Magnus Carlsen", Some(2830)), ("Ann Smith", Some(1800)), ("Ann Smyth", Some(1700))]);
+        tournament.assign_pairing_numbers();
+
+        assert_eq!(tournament.find_player("2").unwrap(), 1);
+        assert_eq!(tournament.find_player("#3").unwrap(), 2);
+        assert_eq!(tournament.find_player("ANN SMITH").unwrap(), 1);
+        assert_eq!(tournament.find_player("carl").unwrap(), 0);
+        assert_eq!(tournament.find_player("m carl").unwrap(), 0);
+        assert!(matches!(tournament.find_player("4"), Err(Error::UnknownPlayer(_))));
+
+        assert_eq!(tournament.find_players("ann sm"), [1, 2]);
+        assert!(matches!(tournament.find_player("ann"), Err(Error::AmbiguousPlayer(_, candidates)) if candidates == ["#2 Ann Smith", "#3 Ann Smyth"]));
+
+    }
+
+    #[test]
+    fn typos_allowed_grow_with_the_query() {
+
+        let tournament = tournament(&[("Bob", None), ("Magnus Carlsen", None), ("Ann", None)]);
+
+        // short searches must match exactly, though they can still be part of a name
+        assert_eq!(tournament.find_players("bo"), [0]);
+        assert!(tournament.find_players("bx").is_empty());
+        assert!(tournament.find_players("anx").is_empty());
+
+        assert_eq!(tournament.find_players("magnis"), [1]);
+        assert_eq!(tournament.find_players("magnus carlsan"), [1]);
+        assert_eq!(tournament.find_players("magnis carlsan"), [1]);
+        assert!(tournament.find_players("magnis carlxxx").is_empty());
+        assert!(tournament.find_players("mxgnis").is_empty());
+
+    }
+
+    #[test]
+    fn find_player_exactly_accepts_no_guesses() {
+
+        let mut tournament = tournament(&[("Ann Smith", Some(1800)), ("Bob", Some(1700))]);
+
+        assert_eq!(tournament.find_player_exactly("ann smith").unwrap(), 0);
+        assert!(tournament.find_player_exactly("ann").is_err());
+        assert!(tournament.remove_player("bbo").is_err());
+        assert!(tournament.players[1].active);
+
+        tournament.assign_pairing_numbers();
+        tournament.remove_player("#2").unwrap();
+        assert!(!tournament.players[1].active);
+
+    }
+}
@@ -0,0 +1,216 @@
+use crate::*;
+
+/// What importing one PGN game did to the tournament.
+pub enum PgnImport {
+    /// The game was pending and now has the PGN result.
+    Recorded { round: usize, board: u32, result: GameResult },
+    /// The PGN agrees with the reported result.
+    Confirmed { round: usize, board: u32 },
+    /// The PGN disagrees with an already reported result, which is left untouched.
+    Mismatch { round: usize, board: u32, reported: GameResult, pgn: GameResult },
+    /// The PGN has no result yet (`*`).
+    Unfinished { round: usize, board: u32 },
+    /// No game between these players in that round.
+    Unmatched { round: usize, white: String, black: String },
+    /// The `Result` tag couldn't be read, so the game was skipped.
+    InvalidResult { round: usize, white: String, black: String, result: String }
+}
+
+impl PgnImport {
+
+    pub fn as_string(&self) -> String {
+        match self {
+            Self::Recorded { round, board, result } => format!("Round {round} board {board}: recorded {}", result.as_notation()),
+            Self::Confirmed { round, board } => format!("Round {round} board {board}: matches the reported result"),
+            Self::Mismatch { round, board, reported, pgn } => {
+                format!("Round {round} board {board}: MISMATCH, PGN says {} but {} was reported", pgn.as_notation(), reported.as_notation())
+            }
+            Self::Unfinished { round, board } => format!("Round {round} board {board}: game still in progress"),
+            Self::Unmatched { round, white, black } => format!("Round {round}: no game {white} vs {black}"),
+            Self::InvalidResult { round, white, black, result } => format!("Round {round}: {white} vs {black} skipped, invalid result \"{result}\"")
+        }
+    }
+
+    pub fn is_mismatch(&self) -> bool {
+        matches!(self, Self::Mismatch { .. })
+    }
+
+    pub fn is_invalid(&self) -> bool {
+        matches!(self, Self::InvalidResult { .. })
+    }
+}
+
+impl Tournament {
+
+    /// One PGN game per board of the round (0-indexed), with an empty move list and the current result.
+    /// The Seven Tag Roster comes first in its standard order, followed by the board and ratings.
+    pub fn round_pgn(&self, round_idx: usize) -> Option<String> {
+
+        let round = self.rounds.get(round_idx)?;
+        let event = if self.name.is_empty() { "?" } else { &self.name };
+
+        let mut pgn = String::new();
+
+        // forfeited games were never played
+        for game in round.games.iter().filter(|game| !game.result.is_forfeit()) {
+
+            let white = &self.players[game.white_player];
+            let black = &self.players[game.black_player];
+
+            let result = match game.result {
+                GameResult::Draw => "1/2-1/2",
+                result => result.as_notation()
+            };
+
+            pgn += &pgn_tag("Event", event);
+            pgn += &pgn_tag("Site", "?");
+            pgn += &pgn_tag("Date", "????.??.??");
+            pgn += &pgn_tag("Round", &(round_idx + 1).to_string());
+            pgn += &pgn_tag("White", &white.name);
+            pgn += &pgn_tag("Black", &black.name);
+            pgn += &pgn_tag("Result", result);
+            pgn += &pgn_tag("Board", &game.board_number.to_string());
+
+            if let Some(rating) = white.rating {
+                pgn += &pgn_tag("WhiteElo", &rating.to_string());
+            }
+
+            if let Some(rating) = black.rating {
+                pgn += &pgn_tag("BlackElo", &rating.to_string());
+            }
+
+            pgn += &format!("\n{result}\n\n");
+
+        }
+
+        Some(pgn)
+
+    }
+
+    /// Applies the `Result` tags of PGN games to the matching games, found by the `Round` tag
+    /// (the current round if missing) and the player names.
+    /// Results that were already reported are never overwritten, differences are returned as [`PgnImport::Mismatch`].
+    /// Games with an unreadable result are skipped as [`PgnImport::InvalidResult`] while the others are still applied.
+    pub fn import_pgn(&mut self, pgn: &str) -> Result<Vec<PgnImport>, Error> {
+
+        if self.rounds.is_empty() {
+            return Err(Error::TournamentNotStarted);
+        }
+
+        if self.finished {
+            return Err(Error::TournamentFinished);
+        }
+
+        let mut imports = Vec::new();
+
+        for tags in parse_pgn_tags(pgn)? {
+
+            let tag = |name: &str| {
+                tags.iter()
+                    .find(|(tag_name, _)| tag_name == name)
+                    .map(|(_, value)| value.as_str())
+            };
+
+            let round_number = match tag("Round").and_then(|round| round.split('.').next()?.parse::<usize>().ok()) {
+                Some(round_number) => round_number,
+                None => self.rounds.len()
+            };
+
+            let white = tag("White").unwrap_or("?").to_string();
+            let black = tag("Black").unwrap_or("?").to_string();
+            let result_tag = tag("Result").unwrap_or("*");
+
+            let Ok(pgn_result) = GameResult::parse(result_tag)
+            else {
+                imports.push(PgnImport::InvalidResult { round: round_number, white, black, result: result_tag.to_string() });
+                continue;
+            };
+
+            let players = &self.players;
+            let same_name = |id: PlayerID, name: &str| players[id].name.to_lowercase() == name.trim().to_lowercase();
+
+            let game = round_number.checked_sub(1)
+                .and_then(|round_idx| self.rounds.get_mut(round_idx))
+                .and_then(|round| round.games.iter_mut().find(|game| same_name(game.white_player, &white) && same_name(game.black_player, &black)));
+
+            let Some(game) = game
+            else {
+                imports.push(PgnImport::Unmatched { round: round_number, white, black });
+                continue;
+            };
+
+            let board = game.board_number;
+
+            imports.push(if pgn_result == GameResult::Pending {
+                PgnImport::Unfinished { round: round_number, board }
+            }
+            else if game.result == GameResult::Pending {
+                game.result = pgn_result;
+                PgnImport::Recorded { round: round_number, board, result: pgn_result }
+            }
+            else if game.result == pgn_result {
+                PgnImport::Confirmed { round: round_number, board }
+            }
+            else {
+                PgnImport::Mismatch { round: round_number, board, reported: game.result, pgn: pgn_result }
+            });
+        }
+
+        Ok(imports)
+
+    }
+}
+
+fn pgn_tag(name: &str, value: &str) -> String {
+    format!("[{name} \"{}\"]\n", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// the tag pairs of every game, a new game starts at the first tag after some movetext
+fn parse_pgn_tags(pgn: &str) -> Result<Vec<Vec<(String, String)>>, Error> {
+
+    let mut games = Vec::new();
+    let mut tags = Vec::new();
+    let mut in_movetext = false;
+
+    for (line_idx, line) in pgn.lines().enumerate() {
+
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('%') {
+            continue;
+        }
+
+        let Some(tag) = line.strip_prefix('[')
+        else {
+            in_movetext = true;
+            continue;
+        };
+
+        if in_movetext {
+            games.push(std::mem::take(&mut tags));
+            in_movetext = false;
+        }
+
+        let invalid_tag = || Error::Parse(format!("Invalid PGN tag on line {}", line_idx + 1));
+
+        let tag = tag.strip_suffix(']').ok_or_else(invalid_tag)?;
+        let (name, value) = tag.split_once(' ').ok_or_else(invalid_tag)?;
+
+        let value = value.trim()
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .ok_or_else(invalid_tag)?
+            .replace("\\\"", "\"")
+            .replace("\\\\", "\\");
+
+        tags.push((name.to_string(), value));
+
+    }
+
+    if !tags.is_empty() {
+        games.push(tags);
+    }
+
+    Ok(games)
+
+}
@@ -1,13 +1,95 @@
 use crate::tournament::*;
 
+use serde::{Deserialize, Serialize};
+
 use std::fmt::Debug;
 
+/// Index into [`Tournament::players`].
 pub type PlayerID = usize;
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Sex {
+    Male,
+    Female
+}
+
+impl Sex {
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "m" | "male" => Some(Self::Male),
+            "f" | "w" | "female" => Some(Self::Female),
+            _ => None
+        }
+    }
+
+    pub fn as_letter(self) -> char {
+        match self {
+            Self::Male => 'm',
+            Self::Female => 'f'
+        }
+    }
+}
+
+/// FIDE titles, strongest first.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum Title {
+    GM,
+    IM,
+    WGM,
+    FM,
+    WIM,
+    CM,
+    WFM,
+    WCM
+}
+
+impl Title {
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_uppercase().as_str() {
+            "GM" => Some(Self::GM),
+            "IM" => Some(Self::IM),
+            "WGM" => Some(Self::WGM),
+            "FM" => Some(Self::FM),
+            "WIM" => Some(Self::WIM),
+            "CM" => Some(Self::CM),
+            "WFM" => Some(Self::WFM),
+            "WCM" => Some(Self::WCM),
+            _ => None
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::GM => "GM",
+            Self::IM => "IM",
+            Self::WGM => "WGM",
+            Self::FM => "FM",
+            Self::WIM => "WIM",
+            Self::CM => "CM",
+            Self::WFM => "WFM",
+            Self::WCM => "WCM"
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Player {
     pub name: String,
     pub rating: Option<u32>,
-    pub active: bool
+    /// False once the player has withdrawn; they are no longer paired.
+    pub active: bool,
+    /// Only used for categories, `None` when unknown.
+    #[serde(default)]
+    pub sex: Option<Sex>,
+    #[serde(default)]
+    pub birth_year: Option<u32>,
+    #[serde(default)]
+    pub title: Option<Title>,
+    /// The official starting rank, assigned when the tournament starts.
+    #[serde(default)]
+    pub pairing_number: Option<u32>
 }
 
 impl Player {
@@ -17,12 +99,24 @@ impl Player {
         Self {
             name,
             rating,
-            active: true
+            active: true,
+            sex: None,
+            birth_year: None,
+            title: None,
+            pairing_number: None
+        }
+    }
+
+    /// The name, preceded by the pairing number once there is one, e.g. `#3 Ann`.
+    pub fn label(&self) -> String {
+        match self.pairing_number {
+            Some(number) => format!("#{number} {}", self.name),
+            None => self.name.clone()
         }
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct PlayerIDList(pub Vec<PlayerID>);
 
 impl PlayerIDList {
@@ -50,10 +144,9 @@ impl PlayerIDList {
 
     }
 
-    // assumes no player can have 2 byes
-    pub fn get_first_player_without_bye(&self, tournament: &Tournament) -> PlayerID {
+    pub fn get_first_player_without_bye(&self, tournament: &Tournament) -> Option<PlayerID> {
         let bye_players = tournament.get_bye_players();
-        self.get_first_player_not_in_list(&bye_players).unwrap()
+        self.get_first_player_not_in_list(&bye_players)
     }
 
     pub fn get_first_player_not_in_list(&self, exclude: &[PlayerID]) -> Option<PlayerID> {
@@ -68,8 +161,9 @@ impl PlayerIDList {
 
     }
 
-    pub fn sort_by_scores_ascending(&mut self, scores: &[f32]) {
-        self.0.sort_by(|&id1, &id2| scores[id2].total_cmp(&scores[id1]));
+    /// Highest score first, players on the same score by pairing number.
+    pub fn sort_by_scores_ascending(&mut self, scores: &[f32], pairing_numbers: &[u32]) {
+        self.0.sort_by(|&id1, &id2| scores[id2].total_cmp(&scores[id1]).then(pairing_numbers[id1].cmp(&pairing_numbers[id2])));
     }
 
     pub fn pair_off_in_order(&self) -> Vec<(PlayerID, PlayerID)> {
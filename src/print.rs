@@ -0,0 +1,79 @@
+use crate::*;
+
+use html::{escape, page};
+
+const STYLE: &str = "body { font-family: sans-serif; }
+table { border-collapse: collapse; width: 100%; }
+th, td { border: 1px solid #000; padding: 0.3em 0.6em; }
+td.number { text-align: right; }
+section { page-break-after: always; }
+section:last-child { page-break-after: auto; }
+.slip { display: inline-block; box-sizing: border-box; width: 48%; margin: 0.5%; padding: 0.8em; border: 1px dashed #000; page-break-inside: avoid; }
+.slip h3 { margin: 0 0 0.5em 0; }
+.slip td { height: 2.5em; }
+.slip td.box { width: 2.5em; }
+@media print { body { margin: 0; } }";
+
+impl Tournament {
+
+    /// Print-ready pages for a round (0-indexed): the pairings by board, an alphabetical "find your board" list
+    /// and one result slip per game, each starting on a new page.
+    pub fn print_html(&self, round_idx: usize) -> Option<String> {
+
+        let round = self.rounds.get(round_idx)?;
+        let title = self.html_title();
+        let round_number = round_idx + 1;
+
+        let name = |id: PlayerID| {
+
+            let player = &self.players[id];
+
+            match player.rating {
+                Some(rating) => format!("{} ({rating})", escape(&player.label())),
+                None => escape(&player.label())
+            }
+        };
+
+        let mut body = format!("<section>\n<h1>{title}: Round {round_number} Pairings</h1>\n<table>\n");
+        body += "<tr><th>Board</th><th>White</th><th>Result</th><th>Black</th></tr>\n";
+
+        for game in round.games.iter() {
+            body += &format!(
+                "<tr><td class=\"number\">{}</td><td>{}</td><td></td><td>{}</td></tr>\n",
+                game.board_number,
+                name(game.white_player),
+                name(game.black_player)
+            );
+        }
+
+        if let Some(bye_player) = round.bye_player {
+            body += &format!("<tr><td></td><td>{}</td><td>bye</td><td></td></tr>\n", name(bye_player));
+        }
+
+        body += "</table>\n</section>\n";
+
+        body += &format!("<section>\n<h1>{title}: Round {round_number} Find Your Board</h1>\n");
+        body += &self.alphabetical_html(round_idx)?;
+        body += "</section>\n<section>\n";
+
+        for game in round.games.iter() {
+            body += &format!(
+                "<div class=\"slip\">\n<h3>{title}: Round {round_number}, Board {}</h3>\n<table>\n\
+                <tr><th></th><th>Player</th><th>Signature</th></tr>\n\
+                <tr><th>White</th><td>{}</td><td></td></tr>\n\
+                <tr><th>Black</th><td>{}</td><td></td></tr>\n\
+                </table>\n<table>\n\
+                <tr><td class=\"box\"></td><td>1-0</td><td class=\"box\"></td><td>½-½</td><td class=\"box\"></td><td>0-1</td></tr>\n\
+                </table>\n</div>\n",
+                game.board_number,
+                name(game.white_player),
+                name(game.black_player)
+            );
+        }
+
+        body += "</section>\n";
+
+        Some(page(&format!("{title}: Round {round_number}"), STYLE, &body))
+
+    }
+}
@@ -0,0 +1,365 @@
+use crate::*;
+
+use category::Category;
+
+/// How a prize is divided between players tied on score.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TieRule {
+    /// Half of the tied prizes are shared equally, the other half go by tiebreak order.
+    Hort,
+    /// The tied prizes are shared equally.
+    Equal,
+    /// Sonneborn-Berger decides, only players tied on it as well share.
+    Tiebreak
+}
+
+impl TieRule {
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "hort" => Some(Self::Hort),
+            "equal" | "split" => Some(Self::Equal),
+            "tiebreak" | "tiebreaks" => Some(Self::Tiebreak),
+            _ => None
+        }
+    }
+}
+
+pub struct Prize {
+    /// `None` for the overall standings.
+    pub category: Option<Category>,
+    /// Starting from 1 within the category.
+    pub place: usize,
+    pub amount: f64
+}
+
+/// Every prize on offer and the rules for handing them out.
+pub struct PrizeFund {
+    pub prizes: Vec<Prize>,
+    pub tie_rule: TieRule,
+    /// A player placing in several categories only receives the highest of those prizes, from the first category (in fund order) if equal.
+    pub one_per_player: bool
+}
+
+/// Money a player receives for one prize, or their share of it.
+pub struct Award {
+    pub player: PlayerID,
+    pub amount: f64,
+    pub description: String
+}
+
+impl PrizeFund {
+
+    /// Reads one prize per line as `[category] <place> <amount>`, e.g. `1 500` or `U1600 1 100`,
+    /// plus the optional settings `tie hort|equal|tiebreak` (default hort) and `multiple` to allow several prizes per player.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+
+        let mut fund = Self {
+            prizes: Vec::new(),
+            tie_rule: TieRule::Hort,
+            one_per_player: true
+        };
+
+        for (line_idx, line) in text.lines().enumerate() {
+
+            let words: Vec<_> = line.split_whitespace().collect();
+            let invalid_line = || Error::Parse(format!("Invalid prize on line {}: \"{}\"", line_idx + 1, line.trim()));
+
+            match words[..] {
+                [] => {}
+                [comment, ..] if comment.starts_with('#') => {}
+                ["tie", rule] => fund.tie_rule = TieRule::from_name(rule).ok_or_else(invalid_line)?,
+                ["multiple"] => fund.one_per_player = false,
+                [place, amount] => fund.prizes.push(Prize {
+                    category: None,
+                    place: place.parse().ok().filter(|&place| place > 0).ok_or_else(invalid_line)?,
+                    amount: amount.parse().map_err(|_| invalid_line())?
+                }),
+                [category, place, amount] => fund.prizes.push(Prize {
+                    category: Some(Category::from_name(category).ok_or_else(invalid_line)?),
+                    place: place.parse().ok().filter(|&place| place > 0).ok_or_else(invalid_line)?,
+                    amount: amount.parse().map_err(|_| invalid_line())?
+                }),
+                _ => return Err(invalid_line())
+            }
+        }
+
+        Ok(fund)
+
+    }
+
+    /// Hands out the prizes on the current standings, categories in the order they first appear in the fund.
+    /// Ages for categories are counted against `year`.
+    pub fn distribute(&self, tournament: &Tournament, year: u32) -> Vec<Award> {
+
+        let mut categories = Vec::new();
+
+        for prize in self.prizes.iter() {
+            if !categories.contains(&prize.category) {
+                categories.push(prize.category);
+            }
+        }
+
+        // players kept to the category of their highest prize, which they're left out of everywhere else
+        let mut kept: Vec<(PlayerID, Option<Category>)> = Vec::new();
+
+        loop {
+
+            let category_awards: Vec<_> = categories.iter()
+                .map(|&category| {
+
+                    let excluded: Vec<_> = kept.iter()
+                        .filter(|(_, kept_category)| *kept_category != category)
+                        .map(|(player, _)| *player)
+                        .collect();
+
+                    self.category_awards(tournament, year, category, &excluded)
+
+                })
+                .collect();
+
+            if !self.one_per_player {
+                return category_awards.into_iter().flatten().collect();
+            }
+
+            let num_categories_won = |player: PlayerID| {
+                category_awards.iter()
+                    .filter(|awards| awards.iter().any(|award| award.player == player))
+                    .count()
+            };
+
+            // the highest prize of anyone with several, the earlier category when equal
+            let highest_duplicate = category_awards.iter()
+                .enumerate()
+                .flat_map(|(category_idx, awards)| awards.iter().map(move |award| (category_idx, award)))
+                .filter(|(_, award)| num_categories_won(award.player) > 1)
+                .max_by(|(category_idx1, award1), (category_idx2, award2)| {
+                    award1.amount.total_cmp(&award2.amount)
+                        .then(category_idx2.cmp(category_idx1))
+                })
+                .map(|(category_idx, award)| (award.player, categories[category_idx]));
+
+            match highest_duplicate {
+                Some(player_category) => kept.push(player_category),
+                None => return category_awards.into_iter().flatten().collect()
+            }
+        }
+    }
+
+    // the awards of one category, ignoring the excluded players
+    fn category_awards(&self, tournament: &Tournament, year: u32, category: Option<Category>, excluded: &[PlayerID]) -> Vec<Award> {
+
+        let mut amounts = Vec::new();
+
+        for prize in self.prizes.iter().filter(|prize| prize.category == category) {
+
+            if amounts.len() < prize.place {
+                amounts.resize(prize.place, 0.0);
+            }
+
+            amounts[prize.place - 1] += prize.amount;
+
+        }
+
+        let ranking: Vec<_> = match category {
+            Some(category) => tournament.category_standings(category, year)
+                .into_iter()
+                .map(|category_standing| category_standing.standing)
+                .collect(),
+            None => tournament.standings()
+        };
+
+        let ranking: Vec<_> = ranking.into_iter()
+            .filter(|standing| !excluded.contains(&standing.player))
+            .collect();
+
+        let mut awards = Vec::new();
+        let mut start = 0;
+
+        while start < ranking.len() && start < amounts.len() {
+
+            let tied = |standing: &Standing| match self.tie_rule {
+                TieRule::Tiebreak => standing.score == ranking[start].score && standing.sonneborn_berger == ranking[start].sonneborn_berger,
+                TieRule::Hort | TieRule::Equal => standing.score == ranking[start].score
+            };
+
+            let end = start + ranking[start..].iter().take_while(|standing| tied(standing)).count();
+            let num_tied = (end - start) as f64;
+
+            let group_amounts: Vec<_> = (start..end)
+                .map(|place| amounts.get(place).copied().unwrap_or(0.0))
+                .collect();
+
+            let total: f64 = group_amounts.iter().sum();
+
+            let places = if end - start == 1 {
+                format!("{} place", ordinal(start + 1))
+            }
+            else {
+                format!("{}-{} place shared", ordinal(start + 1), ordinal(end))
+            };
+
+            let description = match category {
+                Some(category) => format!("{} {places}", category.name()),
+                None => places
+            };
+
+            for (standing, own_amount) in ranking[start..end].iter().zip(group_amounts) {
+
+                let amount = match self.tie_rule {
+                    TieRule::Hort => total / num_tied / 2.0 + own_amount / 2.0,
+                    TieRule::Equal | TieRule::Tiebreak => total / num_tied
+                };
+
+                if amount > 0.0 {
+                    awards.push(Award {
+                        player: standing.player,
+                        amount,
+                        description: description.clone()
+                    });
+                }
+            }
+
+            start = end;
+
+        }
+
+        awards
+
+    }
+}
+
+/// One line per prize winner with their total, highest first, amounts rounded to cents.
+pub fn payout_list(awards: &[Award], players: &[Player]) -> String {
+
+    let mut winners: Vec<PlayerID> = Vec::new();
+
+    for award in awards {
+        if !winners.contains(&award.player) {
+            winners.push(award.player);
+        }
+    }
+
+    let total = |player: PlayerID| -> f64 {
+        awards.iter()
+            .filter(|award| award.player == player)
+            .map(|award| award.amount)
+            .sum()
+    };
+
+    winners.sort_by(|&player1, &player2| total(player2).total_cmp(&total(player1)));
+
+    let mut output = String::new();
+
+    for player in winners {
+
+        let descriptions: Vec<_> = awards.iter()
+            .filter(|award| award.player == player)
+            .map(|award| award.description.as_str())
+            .collect();
+
+        output += &format!("{:>10.2}  {} ({})\n", total(player), players[player].label(), descriptions.join(", "));
+
+    }
+
+    let fund_total: f64 = awards.iter().map(|award| award.amount).sum();
+    output += &format!("{fund_total:>10.2}  Total\n");
+
+    output
+
+}
+
+fn ordinal(number: usize) -> String {
+
+    let suffix = match (number % 10, number % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th"
+    };
+
+    format!("{number}{suffix}")
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    use prize::*;
+    use testing::played_tournament;
+
+    // players 0 to 3 score 1.5, 1.5, 1 and 0, with player 1 ahead of player 0 on Sonneborn-Berger;
+    // the ratings are only for the categories
+    fn tournament(ratings: [u32; 4]) -> Tournament {
+
+        let mut tournament = played_tournament(4, &[
+            (&[(1, 2, GameResult::Draw), (3, 4, GameResult::Win)], None),
+            (&[(1, 4, GameResult::Win), (2, 3, GameResult::Win)], None)
+        ]);
+
+        for (player, rating) in tournament.players.iter_mut().zip(ratings) {
+            player.rating = Some(rating);
+        }
+
+        tournament
+
+    }
+
+    fn amounts(fund: &str, tournament: &Tournament) -> Vec<f64> {
+
+        let awards = PrizeFund::parse(fund).unwrap().distribute(tournament, 2026);
+
+        (0..tournament.players.len())
+            .map(|player| awards.iter().filter(|award| award.player == player).map(|award| award.amount).sum())
+            .collect()
+
+    }
+
+    #[test]
+    fn tied_players_share_by_the_tie_rule() {
+
+        let tournament = tournament([2000; 4]);
+
+        assert_eq!(amounts("1 100\n2 60\n3 20", &tournament), [70.0, 90.0, 20.0, 0.0]);
+        assert_eq!(amounts("tie equal\n1 100\n2 60\n3 20", &tournament), [80.0, 80.0, 20.0, 0.0]);
+        assert_eq!(amounts("tie tiebreak\n1 100\n2 60\n3 20", &tournament), [60.0, 100.0, 20.0, 0.0]);
+
+    }
+
+    #[test]
+    fn tie_groups_crossing_the_last_prize_share_what_is_left() {
+
+        let tournament = tournament([2000; 4]);
+
+        // players 0 and 1 tie for 1st-2nd, only 1st has a prize
+        assert_eq!(amounts("1 100", &tournament), [25.0, 75.0, 0.0, 0.0]);
+        assert_eq!(amounts("tie equal\n1 100", &tournament), [50.0, 50.0, 0.0, 0.0]);
+        assert_eq!(amounts("tie tiebreak\n1 100", &tournament), [0.0, 100.0, 0.0, 0.0]);
+
+        let awards = PrizeFund::parse("1 100").unwrap().distribute(&tournament, 2026);
+        assert!(awards.iter().all(|award| award.description == "1st-2nd place shared"));
+
+    }
+
+    #[test]
+    fn one_prize_per_player_is_their_highest_in_any_order() {
+
+        // player 0 is 2nd overall and 1st under 1600
+        let tournament = tournament([1500, 2000, 2000, 1400]);
+
+        let overall_first = "tie tiebreak\n1 100\n2 60\nU1600 1 50";
+        let category_first = "tie tiebreak\nU1600 1 50\n1 100\n2 60";
+
+        assert_eq!(amounts(overall_first, &tournament), [60.0, 100.0, 0.0, 50.0]);
+        assert_eq!(amounts(category_first, &tournament), [60.0, 100.0, 0.0, 50.0]);
+
+        // a higher category prize is kept instead, moving player 2 up to 2nd overall
+        assert_eq!(amounts("tie tiebreak\n1 100\n2 60\nU1600 1 80", &tournament), [80.0, 100.0, 60.0, 0.0]);
+
+        assert_eq!(amounts("multiple\ntie tiebreak\nU1600 1 50\n1 100\n2 60", &tournament), [110.0, 100.0, 0.0, 0.0]);
+
+    }
+}
@@ -1,10 +1,20 @@
 use crate::player::*;
+use crate::error::*;
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+/// The result of a game from white's perspective.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GameResult {
     Win,
     Draw,
     Loss,
+    /// White wins by forfeit, written `+/-`.
+    ForfeitWin,
+    /// Black wins by forfeit, written `-/+`.
+    ForfeitLoss,
+    /// Neither player scores, e.g. both failed to turn up.
+    DoubleForfeit,
     Pending
 }
 
@@ -12,7 +22,7 @@ impl GameResult {
 
     pub fn score(self) -> f32 {
         match self {
-            Self::Win => 1.0,
+            Self::Win | Self::ForfeitWin => 1.0,
             Self::Draw => 0.5,
             _ => 0.0
         }
@@ -23,20 +33,59 @@ impl GameResult {
             Self::Win => Self::Loss,
             Self::Draw => Self::Draw,
             Self::Loss => Self::Win,
+            Self::ForfeitWin => Self::ForfeitLoss,
+            Self::ForfeitLoss => Self::ForfeitWin,
+            Self::DoubleForfeit => Self::DoubleForfeit,
             Self::Pending => Self::Pending
         }
     }
 
+    pub fn is_forfeit(self) -> bool {
+        matches!(self, Self::ForfeitWin | Self::ForfeitLoss | Self::DoubleForfeit)
+    }
+
+    /// Accepts `w`/`d`/`l`/`u` as well as `1-0`, `½-½` (or `1/2`, `=`), `0-1`,
+    /// the forfeits `+/-` and `-/+`, and `0-0` (or `-/-`) for a double forfeit.
+    /// A bare `+` (a bye in crosstables) and `--` (an option on the command line) are rejected.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        match text.trim().to_lowercase().replace(' ', "").as_str() {
+            "w" | "win" | "1-0" | "1" => Ok(Self::Win),
+            "d" | "draw" | "½-½" | "1/2-1/2" | "½" | "1/2" | "=" | "0.5" => Ok(Self::Draw),
+            "l" | "loss" | "0-1" | "0" => Ok(Self::Loss),
+            "+/-" | "+-" => Ok(Self::ForfeitWin),
+            "-/+" | "-+" => Ok(Self::ForfeitLoss),
+            "0-0" | "-/-" => Ok(Self::DoubleForfeit),
+            "u" | "unreport" | "*" => Ok(Self::Pending),
+            "" => Err(Error::Parse("Missing match result".to_string())),
+            _ => Err(Error::Parse(format!("Invalid match result \"{}\"", text.trim())))
+        }
+    }
+
+    pub fn as_notation(self) -> &'static str {
+        match self {
+            Self::Win => "1-0",
+            Self::Draw => "½-½",
+            Self::Loss => "0-1",
+            Self::ForfeitWin => "+/-",
+            Self::ForfeitLoss => "-/+",
+            Self::DoubleForfeit => "0-0",
+            Self::Pending => "*"
+        }
+    }
+
     pub fn as_letter(self) -> char {
         match self {
             Self::Win => 'W',
             Self::Draw => 'D',
             Self::Loss => 'L',
+            Self::ForfeitWin => '+',
+            Self::ForfeitLoss | Self::DoubleForfeit => '-',
             Self::Pending => ' '
         }
     }
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Game {
     pub white_player: PlayerID,
     pub black_player: PlayerID,
@@ -49,10 +98,10 @@ impl Game {
     pub fn as_string(&self, players: &[Player]) -> String {
 
         if self.result == GameResult::Pending {
-            format!("[{}] {} vs. {}", self.board_number, players[self.white_player].name, players[self.black_player].name)
+            format!("[{}] {} vs. {}", self.board_number, players[self.white_player].label(), players[self.black_player].label())
         }
         else {
-            format!("[{}] {} ({}) vs {} ({})", self.board_number, players[self.white_player].name, self.result.as_letter(), players[self.black_player].name, self.result.opposite().as_letter())
+            format!("[{}] {} ({}) vs {} ({})", self.board_number, players[self.white_player].label(), self.result.as_letter(), players[self.black_player].label(), self.result.opposite().as_letter())
         }
     }
 
@@ -61,11 +110,126 @@ impl Game {
     }
 }
 
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Round {
+    /// Ordered by board number once the round has been added to a tournament.
     pub games: Vec<Game>,
     pub bye_player: Option<PlayerID>
 }
 
+impl Round {
+
+    pub fn pending_boards(&self) -> Vec<u32> {
+        self.games.iter()
+            .filter(|game| game.result == GameResult::Pending)
+            .map(|game| game.board_number)
+            .collect()
+    }
+}
+
+/// Parses results for several boards such as `1:1-0 2:½ 3:0-1`, separated by spaces or commas.
+pub fn parse_board_results(text: &str) -> Result<Vec<(u32, GameResult)>, Error> {
+
+    let mut results: Vec<(u32, GameResult)> = Vec::new();
+
+    for entry in text.split([' ', ',']).filter(|entry| !entry.is_empty()) {
+
+        let Some((board_text, result_text)) = entry.split_once(':')
+        else {
+            return Err(Error::Parse(format!("Expected <board>:<result>, got \"{entry}\"")));
+        };
+
+        let Ok(board_number) = board_text.parse::<u32>()
+        else {
+            return Err(Error::Parse(format!("Invalid board number \"{board_text}\"")));
+        };
+
+        if results.iter().any(|(board, _)| *board == board_number) {
+            return Err(Error::Parse(format!("Board {board_number} is given more than once")));
+        }
+
+        results.push((board_number, GameResult::parse(result_text)?));
+
+    }
+
+    if results.is_empty() {
+        return Err(Error::Parse("No results given".to_string()));
+    }
+
+    Ok(results)
+
+}
+
+/// E.g. `Pending boards: 4, 7` or `All results are in.`
+pub fn pending_boards_message(pending_boards: &[u32]) -> String {
+
+    if pending_boards.is_empty() {
+        return "All results are in.".to_string();
+    }
+
+    let boards: Vec<_> = pending_boards.iter()
+        .map(|board_number| board_number.to_string())
+        .collect();
+
+    format!("Pending boards: {}", boards.join(", "))
+
+}
+
 pub fn has_already_played(player1: PlayerID, player2: PlayerID, already_played: &[PlayerIDList]) -> bool {
     already_played[player1].0.contains(&player2) || already_played[player2].0.contains(&player1)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+
+    use crate::*;
+
+    #[test]
+    fn parse_every_result_notation() {
+
+        let notations = [
+            (GameResult::Win, &["w", "win", "1-0", "1", " W ", "1 - 0"][..]),
+            (GameResult::Draw, &["d", "draw", "½-½", "1/2-1/2", "½", "1/2", "=", "0.5"]),
+            (GameResult::Loss, &["l", "loss", "0-1", "0"]),
+            (GameResult::ForfeitWin, &["+/-", "+-"]),
+            (GameResult::ForfeitLoss, &["-/+", "-+"]),
+            (GameResult::DoubleForfeit, &["0-0", "-/-"]),
+            (GameResult::Pending, &["u", "unreport", "*"])
+        ];
+
+        for (result, texts) in notations {
+            for text in texts {
+                assert!(GameResult::parse(text).ok() == Some(result), "\"{text}\" should parse as {}", result.as_notation());
+            }
+        }
+
+        for result in notations.map(|(result, _)| result) {
+            assert!(GameResult::parse(result.as_notation()).ok() == Some(result));
+        }
+    }
+
+    #[test]
+    fn parse_rejects_unknown_and_ambiguous_results() {
+
+        for text in ["", " ", "+", "-", "--", "2-0", "1-1", "won", "w/l", "½-0"] {
+            assert!(matches!(GameResult::parse(text), Err(Error::Parse(_))), "\"{text}\" should be rejected");
+        }
+    }
+
+    #[test]
+    fn parse_several_board_results() {
+
+        let results = parse_board_results("1:1-0 2:½, 3:0-1,4:+/-  5:*").unwrap();
+
+        let expected = [(1, GameResult::Win), (2, GameResult::Draw), (3, GameResult::Loss), (4, GameResult::ForfeitWin), (5, GameResult::Pending)];
+        assert!(results == expected);
+
+    }
+
+    #[test]
+    fn parse_board_results_rejects_bad_entries() {
+
+        for text in ["", " , ", "1", "1-0", "x:1-0", "-1:1-0", "1:", "1:+", "1:1-0 1:0-1"] {
+            assert!(matches!(parse_board_results(text), Err(Error::Parse(_))), "\"{text}\" should be rejected");
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::*;
+use crate::algorithms::PairingSystem;
+use crate::checker::Violation;
+
+use rand::Rng;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const UNRATED_STRENGTH: f64 = 1200.0;
+const WHITE_ADVANTAGE: f64 = 35.0;
+const MAX_DRAW_CHANCE: f64 = 0.35;
+
+/// Random tournament generator for stress testing the pairing systems.
+pub struct RandomTournament {
+    pub tournament: Tournament,
+    pub violations: Vec<(usize, Violation)>,
+    pub fallbacks: Vec<(usize, PairingSystem)>
+}
+
+impl RandomTournament {
+
+    pub fn generate(num_players: usize, num_rounds: usize, system: PairingSystem, rng: &mut impl Rng) -> Result<Self, Error> {
+
+        let mut tournament = Tournament::new();
+
+        for idx in 0..num_players {
+
+            let rating = rng.gen_bool(0.9).then(|| rng.gen_range(1000..=2400));
+            tournament.add_player(format!("Player {}", idx + 1), rating);
+
+        }
+
+        let mut violations = Vec::new();
+        let mut fallbacks = Vec::new();
+
+        for round_idx in 0..num_rounds {
+
+            let (mut round, used_system) = system.generate_with_fallback(&tournament)?;
+
+            if used_system != system {
+                fallbacks.push((round_idx, used_system));
+            }
+
+            for violation in round.check(&tournament, None) {
+                violations.push((round_idx, violation));
+            }
+
+            round.simulate_results(&tournament.players, rng);
+
+            tournament.start();
+            tournament.rounds.push(round);
+            tournament.number_boards(round_idx, tournament.board_rules.first_board(), &[]);
+
+        }
+
+        Ok(Self {
+            tournament,
+            violations,
+            fallbacks
+        })
+    }
+}
+
+/// Picks a result using Elo expected scores, with draws most likely between evenly matched players.
+pub fn simulate_game(white_rating: Option<u32>, black_rating: Option<u32>, rng: &mut impl Rng) -> GameResult {
+
+    let white_strength = white_rating.map_or(UNRATED_STRENGTH, f64::from) + WHITE_ADVANTAGE;
+    let black_strength = black_rating.map_or(UNRATED_STRENGTH, f64::from);
+
+    let expected_score = 1.0 / (1.0 + 10.0f64.powf((black_strength - white_strength) / 400.0));
+
+    let draw_chance = MAX_DRAW_CHANCE * (1.0 - (expected_score - 0.5).abs() * 2.0);
+    let win_chance = expected_score - draw_chance / 2.0;
+
+    let roll = rng.gen::<f64>();
+
+    if roll < win_chance {
+        GameResult::Win
+    }
+    else if roll < win_chance + draw_chance {
+        GameResult::Draw
+    }
+    else {
+        GameResult::Loss
+    }
+}
+
+impl Round {
+
+    pub fn simulate_results(&mut self, players: &[Player], rng: &mut impl Rng) {
+        for game in self.games.iter_mut() {
+            if game.result == GameResult::Pending {
+                game.result = simulate_game(players[game.white_player].rating, players[game.black_player].rating, rng);
+            }
+        }
+    }
+}
+
+pub struct StressTestReport {
+    pub tournaments: Vec<(u64, RandomTournament)>,
+    pub failed_seeds: Vec<u64>
+}
+
+impl StressTestReport {
+
+    pub fn run(num_tournaments: usize, num_players: usize, num_rounds: usize, system: PairingSystem, first_seed: u64) -> Self {
+
+        let mut tournaments = Vec::new();
+        let mut failed_seeds = Vec::new();
+
+        for seed in first_seed..first_seed + num_tournaments as u64 {
+
+            let result = std::panic::catch_unwind(|| {
+                let mut rng = StdRng::seed_from_u64(seed);
+                RandomTournament::generate(num_players, num_rounds, system, &mut rng)
+            });
+
+            match result {
+                Ok(Ok(random_tournament)) => tournaments.push((seed, random_tournament)),
+                _ => failed_seeds.push(seed)
+            }
+        }
+
+        Self {
+            tournaments,
+            failed_seeds
+        }
+    }
+
+    pub fn as_string(&self) -> String {
+
+        let mut violation_counts: Vec<(&str, usize)> = Vec::new();
+        let mut max_colour_difference = 0;
+        let mut num_fallbacks = 0;
+
+        for (_, random_tournament) in self.tournaments.iter() {
+
+            num_fallbacks += random_tournament.fallbacks.len();
+
+            for (_, violation) in random_tournament.violations.iter() {
+                match violation_counts.iter_mut().find(|(kind, _)| *kind == violation.kind()) {
+                    Some((_, count)) => *count += 1,
+                    None => violation_counts.push((violation.kind(), 1))
+                }
+            }
+
+            for difference in random_tournament.tournament.get_colour_differences() {
+                max_colour_difference = max_colour_difference.max(difference.abs());
+            }
+        }
+
+        let mut report = format!("Tournaments generated: {}\n", self.tournaments.len());
+        report += &format!("Pairing failures: {}\n", self.failed_seeds.len());
+
+        for seed in self.failed_seeds.iter() {
+            report += &format!("- seed {seed}\n");
+        }
+
+        report += &format!("Rounds paired by a fallback system: {num_fallbacks}\n");
+        report += &format!("Largest colour difference: {max_colour_difference}");
+
+        if violation_counts.is_empty() {
+            report += "\nNo rule violations.";
+        }
+
+        for (kind, count) in violation_counts {
+            report += &format!("\n{kind}: {count}");
+        }
+
+        report
+
+    }
+}
@@ -0,0 +1,409 @@
+use crate::*;
+
+use rand::Rng;
+
+use serde_json::{json, Value};
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const MAX_BODY_SIZE: usize = 1 << 20;
+
+// how long a client may take to send its request before the connection is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct Request {
+    method: String,
+    path: Vec<String>,
+    content_type: String,
+    token: Option<String>,
+    body: Value
+}
+
+/// Who may use the API: every request but a CORS preflight needs `Authorization: Bearer <token>`,
+/// and browsers only let pages from `allowed_origin` read responses.
+pub struct Access {
+    pub token: String,
+    pub allowed_origin: Option<String>
+}
+
+impl Access {
+
+    /// A random token, to be shown to the arbiter when the server starts.
+    pub fn new(allowed_origin: Option<String>) -> Self {
+
+        let mut rng = rand::thread_rng();
+        let token = (0..32).map(|_| format!("{:x}", rng.gen_range(0..16))).collect();
+
+        Self {
+            token,
+            allowed_origin
+        }
+    }
+}
+
+struct Response {
+    status: u16,
+    body: Value
+}
+
+impl Response {
+
+    fn ok(body: Value) -> Self {
+        Self {
+            status: 200,
+            body
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() })
+        }
+    }
+
+    fn from_error(err: &Error) -> Self {
+
+        let status = match err {
+            Error::Parse(_) | Error::InvalidCommand(_) | Error::AmbiguousPlayer(..) => 400,
+            Error::UnknownPlayer(_) | Error::UnknownBoard(_) | Error::UnknownSection(_) => 404,
+            Error::PairingImpossible(_) | Error::TournamentNotStarted | Error::TournamentFinished | Error::AllRoundsPaired(_) | Error::GamesPending(_) => 409,
+            Error::Io(_) | Error::File(..) => 500
+        };
+
+        Self::error(status, err.to_string())
+
+    }
+}
+
+/// Serves one section of the event as a JSON API until the process is stopped, saving to `save_path` after every change.
+/// POST bodies must be sent as `application/json`.
+///
+/// - `GET /api/tournament`, `/api/players`, `/api/rounds`, `/api/rounds/<n>`, `/api/games`, `/api/standings`
+/// - `POST /api/players` with `{"name": ..., "rating": ...}`
+/// - `POST /api/players/<pairing number>/withdraw`, or the full name before the first round
+/// - `POST /api/results` with `{"board": ..., "result": "1-0"}`, plus `"round"` and the required `"by"` to correct an earlier round
+/// - `POST /api/rounds` to pair the next round
+///
+/// The token can only be left empty when listening on a loopback address.
+pub fn serve(event: Event, section_idx: usize, address: &str, save_path: Option<String>, access: Access) -> Result<(), Error> {
+
+    if section_idx >= event.sections.len() {
+        return Err(Error::UnknownSection(section_idx.to_string()));
+    }
+
+    let listener = TcpListener::bind(address)?;
+
+    if access.token.trim().is_empty() && !listener.local_addr()?.ip().is_loopback() {
+        return Err(Error::InvalidCommand(format!("An API token is needed to serve on {address}")));
+    }
+
+    let event = Arc::new(Mutex::new(event));
+    let save_path = Arc::new(save_path);
+    let access = Arc::new(access);
+
+    for stream in listener.incoming() {
+
+        let Ok(stream) = stream
+        else {
+            continue;
+        };
+
+        if stream.set_read_timeout(Some(READ_TIMEOUT)).is_err() {
+            continue;
+        }
+
+        let event = event.clone();
+        let save_path = save_path.clone();
+        let access = access.clone();
+
+        std::thread::spawn(move || handle_connection(stream, &event, section_idx, save_path.as_deref(), &access));
+    }
+
+    Ok(())
+
+}
+
+fn handle_connection(mut stream: TcpStream, event: &Mutex<Event>, section_idx: usize, save_path: Option<&str>, access: &Access) {
+
+    let response = match read_request(&stream) {
+        Ok(Some(request)) if request.method == "OPTIONS" => Response::ok(Value::Null),
+        Ok(Some(request)) if request.token.as_deref() != Some(access.token.as_str()) => Response::error(401, "Missing or wrong API token"),
+        // a cross-site form can only send text/plain, urlencoded or multipart bodies
+        Ok(Some(request)) if request.method == "POST" && request.content_type != "application/json" => {
+            Response::error(415, "POST bodies must be application/json")
+        }
+        Ok(Some(request)) => {
+
+            // the lock is only taken once the whole request has arrived, so a slow client can't stall the others
+            let mut event = event.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let response = route(&request, &mut event, section_idx);
+
+            if request.method == "POST" && response.status == 200 {
+                if let Some(path) = save_path {
+                    if let Err(err) = event.save(path) {
+                        eprintln!("Error: Could not save {path}: {err}.");
+                    }
+                }
+            }
+
+            response
+
+        }
+        Ok(None) => return,
+        // the client stopped sending before the request was complete
+        Err(Error::Io(err)) if matches!(err.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => return,
+        Err(err) => Response::from_error(&err)
+    };
+
+    let body = response.body.to_string();
+
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        415 => "Unsupported Media Type",
+        _ => "Internal Server Error"
+    };
+
+    let cors = match &access.allowed_origin {
+        Some(origin) => format!(
+            "Access-Control-Allow-Origin: {origin}\r\nVary: Origin\r\nAccess-Control-Allow-Methods: GET, POST, OPTIONS\r\nAccess-Control-Allow-Headers: Content-Type, Authorization\r\n"
+        ),
+        None => String::new()
+    };
+
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{cors}Connection: close\r\n\r\n{body}",
+        response.status,
+        body.len()
+    );
+}
+
+fn read_request(stream: &TcpStream) -> Result<Option<Request>, Error> {
+
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+
+    if reader.read_line(&mut request_line)? == 0 {
+        return Ok(None);
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_uppercase();
+    let target = parts.next().unwrap_or_default();
+
+    let mut content_length = 0;
+    let mut content_type = String::new();
+    let mut token = None;
+
+    loop {
+
+        let mut header = String::new();
+
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            let name = name.trim();
+
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().map_err(|_| Error::Parse("Invalid Content-Length".to_string()))?;
+            }
+            else if name.eq_ignore_ascii_case("content-type") {
+                // drop parameters such as `; charset=utf-8`
+                content_type = value.split(';').next().unwrap_or_default().trim().to_lowercase();
+            }
+            else if name.eq_ignore_ascii_case("authorization") {
+                token = value.trim().strip_prefix("Bearer ").map(|token| token.trim().to_string());
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_SIZE {
+        return Err(Error::Parse("Request body too large".to_string()));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let body = if body.is_empty() {
+        Value::Null
+    }
+    else {
+        serde_json::from_slice(&body).map_err(|err| Error::Parse(format!("Invalid JSON: {err}")))?
+    };
+
+    let path = target.split('?')
+        .next()
+        .unwrap_or_default()
+        .split('/')
+        .filter(|part| !part.is_empty())
+        .map(|part| part.to_string())
+        .collect();
+
+    Ok(Some(Request {
+        method,
+        path,
+        content_type,
+        token,
+        body
+    }))
+}
+
+fn route(request: &Request, event: &mut Event, section_idx: usize) -> Response {
+
+    let path: Vec<_> = request.path.iter().map(|part| part.as_str()).collect();
+
+    // pairing goes through the event so board numbers continue from the other sections
+    if let ("POST", ["api", "rounds"]) = (request.method.as_str(), path.as_slice()) {
+
+        let result = event.pair_next_round(section_idx)
+            .map(|used_system| {
+
+                let tournament = &event.sections[section_idx].tournament;
+
+                json!({
+                    "system": used_system.name(),
+                    "round": tournament.round_json(tournament.rounds.len() - 1)
+                })
+            });
+
+        return match result {
+            Ok(body) => Response::ok(body),
+            Err(err) => Response::from_error(&err)
+        };
+    }
+
+    let tournament = &mut event.sections[section_idx].tournament;
+
+    let result = match (request.method.as_str(), path.as_slice()) {
+        ("GET", ["api", "tournament"]) => Ok(json!({
+            "name": tournament.name,
+            "system": tournament.system.name(),
+            "total_rounds": tournament.total_rounds,
+            "finished": tournament.finished,
+            "players": tournament.players_json(),
+            "rounds": tournament.rounds_json(),
+            "standings": tournament.standings_json()
+        })),
+        ("GET", ["api", "players"]) => Ok(tournament.players_json()),
+        ("GET", ["api", "rounds"]) => Ok(tournament.rounds_json()),
+        ("GET", ["api", "rounds", number]) => {
+
+            let round = number.parse::<usize>()
+                .ok()
+                .and_then(|number| tournament.round_json(number.checked_sub(1)?));
+
+            match round {
+                Some(round) => Ok(round),
+                None => return Response::error(404, format!("No round {number}"))
+            }
+        }
+        ("GET", ["api", "games"]) => match tournament.rounds.len() {
+            0 => Err(Error::TournamentNotStarted),
+            num_rounds => Ok(tournament.round_json(num_rounds - 1).unwrap())
+        },
+        ("GET", ["api", "standings"]) => Ok(tournament.standings_json()),
+        ("POST", ["api", "players"]) => add_player(&request.body, event, section_idx),
+        ("POST", ["api", "players", number, "withdraw"]) => withdraw_player(number, tournament),
+        ("POST", ["api", "results"]) => report_result(&request.body, tournament),
+        (_, ["api", ..]) => return Response::error(405, "Unsupported method or path"),
+        _ => return Response::error(404, "Not found")
+    };
+
+    match result {
+        Ok(body) => Response::ok(body),
+        Err(err) => Response::from_error(&err)
+    }
+}
+
+fn add_player(body: &Value, event: &mut Event, section_idx: usize) -> Result<Value, Error> {
+
+    let Some(name) = body["name"].as_str().filter(|name| !name.trim().is_empty())
+    else {
+        return Err(Error::Parse("Missing player name".to_string()));
+    };
+
+    let rating = match &body["rating"] {
+        Value::Null => None,
+        rating => Some(
+            rating.as_u64()
+                .and_then(|rating| u32::try_from(rating).ok())
+                .ok_or_else(|| Error::Parse(format!("Invalid elo \"{rating}\"")))?
+        )
+    };
+
+    event.add_player(section_idx, name.trim().to_string(), rating)?;
+
+    let players = &event.sections[section_idx].tournament.players;
+
+    Ok(json!({
+        "id": players.len() - 1,
+        "pairing_number": players.last().and_then(|player| player.pairing_number)
+    }))
+
+}
+
+// by pairing number as the arbiter sees everywhere else, or by full name before the numbers are assigned
+fn withdraw_player(number: &str, tournament: &mut Tournament) -> Result<Value, Error> {
+
+    let player = tournament.find_player_exactly(number)?;
+    tournament.players[player].active = false;
+
+    Ok(Value::Null)
+
+}
+
+fn report_result(body: &Value, tournament: &mut Tournament) -> Result<Value, Error> {
+
+    let Some(board_number) = body["board"].as_u64().and_then(|board| u32::try_from(board).ok())
+    else {
+        return Err(Error::Parse("Missing or invalid board number".to_string()));
+    };
+
+    let Some(result) = body["result"].as_str()
+    else {
+        return Err(Error::Parse("Missing result".to_string()));
+    };
+
+    let result = GameResult::parse(result)?;
+
+    // a round number corrects an earlier result, answering with the rounds that might have been paired differently
+    if let Some(round_number) = body["round"].as_u64() {
+
+        let round_idx = (round_number as usize).checked_sub(1)
+            .ok_or_else(|| Error::Parse(format!("No round {round_number}")))?;
+
+        // the audit log needs to say who changed the result
+        let Some(by) = body["by"].as_str().filter(|by| !by.trim().is_empty())
+        else {
+            return Err(Error::Parse("Missing \"by\" for a correction".to_string()));
+        };
+
+        let affected = tournament.correct_result(round_idx, board_number, result, by.trim())?;
+
+        let rounds: Vec<_> = affected.iter()
+            .map(|(round_idx, players)| json!({
+                "round": round_idx + 1,
+                "players": players
+            }))
+            .collect();
+
+        return Ok(json!({ "affected_rounds": rounds }));
+
+    }
+
+    tournament.report_result(board_number, result)?;
+
+    Ok(Value::Null)
+
+}
@@ -0,0 +1,100 @@
+use crate::*;
+
+/// One row of the standings table.
+pub struct Standing {
+    pub player: PlayerID,
+    /// Shared by players tied on score and Sonneborn-Berger.
+    pub place: usize,
+    pub score: f32,
+    pub sonneborn_berger: f32,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    pub byes: u32
+}
+
+impl Tournament {
+
+    /// Every player (including withdrawn ones) ordered by score, then Sonneborn-Berger.
+    pub fn standings(&self) -> Vec<Standing> {
+
+        let mut player_ids = self.get_all_player_ids().0;
+
+        let scores = self.get_player_scores();
+
+        let sb_scores: Vec<_> =
+            player_ids.iter()
+                .map(|player| self.calc_sonneborn_berger_score(*player, &scores))
+                .collect();
+
+        player_ids.sort_by(|&id1, &id2| {
+            scores[id2].total_cmp(&scores[id1])
+                .then(sb_scores[id2].total_cmp(&sb_scores[id1]))
+        });
+
+        let mut stats: Vec<_> =
+            player_ids.iter()
+                .map(|_| (0, 0, 0, 0))
+                .collect();
+
+        for round in self.rounds.iter() {
+
+            if let Some(bye_player) = round.bye_player {
+                stats[bye_player].3 += 1;
+            }
+
+            for game in round.games.iter() {
+                match game.result {
+                    GameResult::Win | GameResult::ForfeitWin => {
+                        stats[game.white_player].0 += 1;
+                        stats[game.black_player].2 += 1;
+                    }
+                    GameResult::Draw => {
+                        stats[game.white_player].1 += 1;
+                        stats[game.black_player].1 += 1;
+                    }
+                    GameResult::Loss | GameResult::ForfeitLoss => {
+                        stats[game.white_player].2 += 1;
+                        stats[game.black_player].0 += 1;
+                    }
+                    GameResult::DoubleForfeit => {
+                        stats[game.white_player].2 += 1;
+                        stats[game.black_player].2 += 1;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut prev_score = f32::MIN;
+        let mut prev_sb_score = f32::MIN;
+        let mut placing = 0;
+
+        player_ids.iter()
+            .enumerate()
+            .map(|(idx, &id)| {
+
+                let score = scores[id];
+                let sb_score = sb_scores[id];
+                let (wins, draws, losses, byes) = stats[id];
+
+                if score != prev_score || sb_score != prev_sb_score {
+                    placing = idx + 1;
+                    prev_score = score;
+                    prev_sb_score = sb_score;
+                }
+
+                Standing {
+                    player: id,
+                    place: placing,
+                    score,
+                    sonneborn_berger: sb_score,
+                    wins,
+                    draws,
+                    losses,
+                    byes
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,35 @@
+use crate::*;
+
+/// (white, black, result) by pairing number.
+pub type PlayedGame = (u32, u32, GameResult);
+
+/// Players numbered 1 to `num_players` by rating, with rounds of games and the bye given by pairing number.
+/// Player IDs are the pairing numbers less one, and boards are numbered in the order the games are given.
+pub fn played_tournament(num_players: u32, rounds: &[(&[PlayedGame], Option<u32>)]) -> Tournament {
+
+    let mut tournament = Tournament::new();
+
+    for number in 1..=num_players {
+        tournament.add_player(format!("Player {number}"), Some(2400 - number * 10));
+    }
+
+    tournament.assign_pairing_numbers();
+
+    for &(games, bye) in rounds {
+        tournament.rounds.push(Round {
+            games: games.iter()
+                .enumerate()
+                .map(|(idx, &(white, black, result))| Game {
+                    white_player: white as PlayerID - 1,
+                    black_player: black as PlayerID - 1,
+                    board_number: idx as u32 + 1,
+                    result
+                })
+                .collect(),
+            bye_player: bye.map(|number| number as PlayerID - 1)
+        });
+    }
+
+    tournament
+
+}
@@ -0,0 +1,194 @@
+use crate::*;
+
+use category::Category;
+
+/// Plain text views of a tournament for the terminal, players shown with their pairing number.
+impl Tournament {
+
+    pub fn round_text(&self, round_idx: usize) -> Option<String> {
+
+        let round = self.rounds.get(round_idx)?;
+
+        let mut output = format!("====Round {} Pairings====\n", round_idx + 1);
+        output += "[Board #] White vs Black\n";
+        output += "-------------------------------------\n";
+
+        for game in round.games.iter() {
+            output += &game.as_string(&self.players);
+            output += "\n";
+        }
+
+        if let Some(bye_player) = round.bye_player {
+            output += &format!("Bye: {}\n", self.players[bye_player].label());
+        }
+
+        Some(output)
+
+    }
+
+    pub fn alphabetical_text(&self, round_idx: usize) -> Option<String> {
+
+        let label = |id: PlayerID| self.players[id].label();
+        let pairings = self.alphabetical_pairings(round_idx)?;
+
+        let name_width = pairings.iter()
+            .map(|(player, _)| label(*player).chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+
+        let mut output = format!("====Round {} Pairings by Name====\n", round_idx + 1);
+        output += &format!("{:<name_width$} | Board | Colour | Opponent\n", "Name");
+
+        for (player, seat) in pairings {
+            output += &match seat {
+                Seat::Board { board, white, opponent, .. } => {
+                    format!("{:<name_width$} | {board: >5} | {} | {}\n", label(player), if white { "White " } else { "Black " }, label(opponent))
+                }
+                Seat::Bye => format!("{:<name_width$} |       |        | Bye\n", label(player))
+            };
+        }
+
+        Some(output)
+
+    }
+
+    pub fn history_text(&self, player: PlayerID) -> String {
+
+        let label = |id: PlayerID| self.players[id].label();
+
+        let mut output = format!("===={} (score {:.1})====\n", label(player), self.calc_score(player));
+        output += "Round | Board | Colour | Result | Opponent\n";
+
+        for (round_idx, seat) in self.history(player).into_iter().enumerate() {
+            output += &match seat {
+                Some(Seat::Board { board, white, opponent, result }) => format!(
+                    "{: >5} | {board: >5} | {} |   {}    | {}\n",
+                    round_idx + 1,
+                    if white { "White " } else { "Black " },
+                    result.as_letter(),
+                    label(opponent)
+                ),
+                Some(Seat::Bye) => format!("{: >5} |       |        |   W    | Bye\n", round_idx + 1),
+                None => format!("{: >5} |       |        |        | Not paired\n", round_idx + 1)
+            };
+        }
+
+        output
+
+    }
+
+    pub fn standings_text(&self) -> String {
+
+        let mut output = if self.finished {
+            "====Final Standings====\n".to_string()
+        }
+        else {
+            format!("====Round {} Standings====\n", self.round_label())
+        };
+        output += "## | Score | SB Score | W/D/L/B | Name\n";
+        output += "---|-------|----------|---------|------------\n";
+
+        for standing in self.standings() {
+
+            let withdraw_star = if self.players[standing.player].active {
+                ' '
+            }
+            else {
+                '*'
+            };
+
+            output += &format!(
+                "{: >2} | {: >5.1}{withdraw_star}| {: >8.2} | {}/{}/{}/{} | {}\n",
+                standing.place,
+                standing.score,
+                standing.sonneborn_berger,
+                standing.wins,
+                standing.draws,
+                standing.losses,
+                standing.byes,
+                self.players[standing.player].label()
+            );
+        }
+
+        output
+
+    }
+
+    pub fn category_standings_text(&self, category: Category, year: u32) -> String {
+
+        let mut output = format!("===={} Standings====\n", category.name());
+        output += "## | Overall | Score | SB Score | Name\n";
+        output += "---|---------|-------|----------|------------\n";
+
+        for category_standing in self.category_standings(category, year) {
+
+            let standing = &category_standing.standing;
+
+            let withdraw_star = if self.players[standing.player].active {
+                ' '
+            }
+            else {
+                '*'
+            };
+
+            output += &format!(
+                "{: >2} | {: >7} | {: >5.1}{withdraw_star}| {: >8.2} | {}\n",
+                category_standing.place,
+                standing.place,
+                standing.score,
+                standing.sonneborn_berger,
+                self.players[standing.player].label()
+            );
+        }
+
+        output
+
+    }
+
+    pub fn crosstable_text(&self) -> String {
+
+        let mut output = format!("====Round {} Crosstable====\n", self.rounds.len());
+        output += &self.crosstable().as_string(&self.players);
+
+        output
+
+    }
+
+    /// The planned and played rounds, with the recommended count for the number of players.
+    pub fn rounds_text(&self) -> String {
+
+        let planned = match self.total_rounds {
+            Some(total_rounds) => format!("{} of {total_rounds} rounds paired", self.rounds.len()),
+            None => format!("{} rounds paired, number of rounds not set", self.rounds.len())
+        };
+
+        let state = if self.finished { ", finished" } else { "" };
+
+        format!("{planned}{state} ({} recommended for {} players)\n", self.recommended_rounds(), self.get_active_player_ids().0.len())
+
+    }
+
+    // e.g. "3/7" when the number of rounds is set
+    fn round_label(&self) -> String {
+        match self.total_rounds {
+            Some(total_rounds) => format!("{}/{total_rounds}", self.rounds.len()),
+            None => self.rounds.len().to_string()
+        }
+    }
+}
+
+/// One line per later round whose pairings a corrected result might have changed.
+pub fn correction_warnings(affected: &[(usize, Vec<PlayerID>)], players: &[Player]) -> Vec<String> {
+    affected.iter()
+        .map(|(round_idx, affected_players)| {
+
+            let names: Vec<_> = affected_players.iter()
+                .map(|&player| players[player].label())
+                .collect();
+
+            format!("Round {} might have been paired differently for {}", round_idx + 1, names.join(", "))
+
+        })
+        .collect()
+}
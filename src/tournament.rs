@@ -1,42 +1,280 @@
 use crate::player::*;
 use crate::round::*;
 use crate::pairing::*;
+use crate::error::*;
+use crate::algorithms::PairingSystem;
+use crate::category::{self, Category};
+use crate::boards::BoardRules;
+use crate::correction::{Correction, Reopening};
 
+use serde::{Deserialize, Serialize};
+
+/// The players of an event and every round paired so far.
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct Tournament {
+    /// Shown as the title of published documents.
+    #[serde(default)]
+    pub name: String,
     pub players: Vec<Player>,
-    pub rounds: Vec<Round>
+    pub rounds: Vec<Round>,
+    /// Used for every round after the first, which is paired by seeding.
+    #[serde(default)]
+    pub system: PairingSystem,
+    /// Ranked separately alongside the overall standings when publishing.
+    #[serde(default)]
+    pub categories: Vec<Category>,
+    /// The planned number of rounds, no more can be paired once they're all played.
+    #[serde(default)]
+    pub total_rounds: Option<usize>,
+    /// Set by [`Tournament::finish`] and cleared by [`Tournament::reopen`], no results can be changed and no rounds paired.
+    #[serde(default)]
+    pub finished: bool,
+    #[serde(default)]
+    pub board_rules: BoardRules,
+    /// Every result changed after the fact, oldest first.
+    #[serde(default)]
+    pub corrections: Vec<Correction>,
+    /// Every time the tournament was unlocked after finishing, oldest first.
+    #[serde(default)]
+    pub reopenings: Vec<Reopening>,
+    /// The year ages are counted against for age categories, set when the first round is paired.
+    #[serde(default)]
+    pub year: Option<u32>
 }
 
 impl Tournament {
 
     pub fn new() -> Self {
         Self {
+            name: String::new(),
             players: Vec::new(),
-            rounds: Vec::new()
+            rounds: Vec::new(),
+            system: PairingSystem::Dutch,
+            categories: Vec::new(),
+            total_rounds: None,
+            finished: false,
+            board_rules: BoardRules::default(),
+            corrections: Vec::new(),
+            reopenings: Vec::new(),
+            year: None
+        }
+    }
+
+    /// Reads a tournament saved with [`Tournament::save`].
+    pub fn load(path: &str) -> Result<Self, Error> {
+        serde_json::from_str(&std::fs::read_to_string(path).map_err(Error::file(path))?)
+            .map_err(|err| Error::Parse(format!("Invalid tournament file \"{path}\": {err}")))
+    }
+
+    /// Saves the tournament as JSON.
+    pub fn save(&self, path: &str) -> Result<(), Error> {
+
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|err| Error::Parse(err.to_string()))?;
+
+        std::fs::write(path, json).map_err(Error::file(path))?;
+
+        Ok(())
+
+    }
+
+    /// Reads one player per line, as `name[, rating[, sex[, birth year[, title]]]]` where any but the name may be blank.
+    pub fn add_players_from_file(&mut self, path: &str) -> Result<(), Error> {
+
+        let lines = std::fs::read_to_string(path).map_err(Error::file(path))?;
+
+        for line in lines.lines().filter(|line| !line.trim().is_empty()) {
+
+            let mut parts = line.split(',').map(|part| part.trim());
+
+            let name = parts.next().unwrap().to_owned();
+
+            let rating = match parts.next().filter(|part| !part.is_empty()) {
+                Some(string) => {
+
+                    let Ok(rating_val) = string.parse()
+                    else {
+                        return Err(Error::Parse(format!("Invalid elo \"{string}\"")));
+                    };
+
+                    Some(rating_val)
+
+                }
+                None => None
+            };
+
+            let sex = match parts.next().filter(|part| !part.is_empty()) {
+                Some(string) => Some(Sex::from_name(string).ok_or_else(|| Error::Parse(format!("Invalid sex \"{string}\"")))?),
+                None => None
+            };
+
+            let birth_year = match parts.next().filter(|part| !part.is_empty()) {
+                Some(string) => Some(string.parse().map_err(|_| Error::Parse(format!("Invalid birth year \"{string}\"")))?),
+                None => None
+            };
+
+            let title = match parts.next().filter(|part| !part.is_empty()) {
+                Some(string) => Some(Title::from_name(string).ok_or_else(|| Error::Parse(format!("Invalid title \"{string}\"")))?),
+                None => None
+            };
+
+            self.add_player(name, rating);
+
+            let player = self.players.last_mut().unwrap();
+            player.sex = sex;
+            player.birth_year = birth_year;
+            player.title = title;
+
         }
+
+        Ok(())
+
     }
 
     pub fn started(&self) -> bool {
         !self.rounds.is_empty()
     }
 
-    pub fn add_player(&mut self, name: String, rating: Option<u32>) {
-        self.players.push(Player::new(name, rating));
+    /// Assigns the pairing numbers and fixes the year ages are counted against, before the first round is added.
+    /// Does nothing once the tournament has started.
+    pub fn start(&mut self) {
+        if !self.started() {
+            self.assign_pairing_numbers();
+            self.year.get_or_insert_with(category::current_year);
+        }
+    }
+
+    pub fn current_round(&self) -> Option<&Round> {
+        self.rounds.last()
     }
 
-    pub fn remove_player(&mut self, name: &str) -> bool {
-        
-        let mut found = false;
+    /// Rounds still to be paired, if the number of rounds is known.
+    pub fn rounds_remaining(&self) -> Option<usize> {
+        self.total_rounds.map(|total_rounds| total_rounds.saturating_sub(self.rounds.len()))
+    }
+
+    /// Whether the next round to be paired is the last one.
+    pub fn pairing_final_round(&self) -> bool {
+        self.rounds_remaining() == Some(1)
+    }
+
+    /// The fewest rounds that can leave a single player with a perfect score, for the active players.
+    pub fn recommended_rounds(&self) -> usize {
+
+        let num_players = self.get_active_player_ids().0.len();
+
+        (usize::BITS - num_players.saturating_sub(1).leading_zeros()).max(1) as usize
+
+    }
+
+    /// Locks the results once every game has been played.
+    pub fn finish(&mut self) -> Result<(), Error> {
+
+        let round = self.current_round().ok_or(Error::TournamentNotStarted)?;
+        let pending_boards = round.pending_boards();
+
+        if !pending_boards.is_empty() {
+            return Err(Error::GamesPending(pending_boards));
+        }
+
+        self.finished = true;
+
+        Ok(())
+
+    }
+
+    /// Pairs and adds the next round, returning the system that was actually used
+    /// (a less strict one is used when the tournament's system can't find a pairing).
+    pub fn pair_next_round(&mut self) -> Result<PairingSystem, Error> {
 
-        for player in self.players.iter_mut() {
-            if player.name.to_lowercase() == *name {
-                player.active = false;
-                found = true;
-                break;
+        if self.finished {
+            return Err(Error::TournamentFinished);
+        }
+
+        if let Some(total_rounds) = self.total_rounds.filter(|_| self.rounds_remaining() == Some(0)) {
+            return Err(Error::AllRoundsPaired(total_rounds));
+        }
+
+        if let Some(prev_round) = self.current_round() {
+
+            let pending_boards = prev_round.pending_boards();
+
+            if !pending_boards.is_empty() {
+                return Err(Error::GamesPending(pending_boards));
             }
         }
 
-        found
+        self.start();
+
+        let (round, used_system) = self.system.generate_with_fallback(self)?;
+
+        self.rounds.push(round);
+        self.number_boards(self.rounds.len() - 1, self.board_rules.first_board(), &[]);
+
+        Ok(used_system)
+
+    }
+
+    /// Sets the result of a game in the current round, from white's perspective.
+    pub fn report_result(&mut self, board_number: u32, result: GameResult) -> Result<(), Error> {
+
+        if self.finished {
+            return Err(Error::TournamentFinished);
+        }
+
+        let round = self.rounds.last_mut().ok_or(Error::TournamentNotStarted)?;
+
+        let game = round.games.iter_mut()
+            .find(|game| game.board_number == board_number)
+            .ok_or(Error::UnknownBoard(board_number))?;
+
+        game.result = result;
+
+        Ok(())
+
+    }
+
+    /// Reports several results of the current round at once. Nothing is changed unless every board exists.
+    pub fn report_results(&mut self, results: &[(u32, GameResult)]) -> Result<(), Error> {
+
+        if self.finished {
+            return Err(Error::TournamentFinished);
+        }
+
+        let round = self.rounds.last_mut().ok_or(Error::TournamentNotStarted)?;
+
+        if let Some(&(board_number, _)) = results.iter().find(|(board_number, _)| round.games.iter().all(|game| game.board_number != *board_number)) {
+            return Err(Error::UnknownBoard(board_number));
+        }
+
+        for &(board_number, result) in results.iter() {
+            round.games.iter_mut()
+                .filter(|game| game.board_number == board_number)
+                .for_each(|game| game.result = result);
+        }
+
+        Ok(())
+
+    }
+
+    /// Players joining after the start get the next pairing number.
+    pub fn add_player(&mut self, name: String, rating: Option<u32>) {
+
+        self.players.push(Player::new(name, rating));
+
+        if self.started() {
+            self.assign_pairing_numbers();
+        }
+    }
+
+    /// Withdraws the player with the pairing number or full name (ignoring case) from future rounds.
+    /// Unlike most commands no partial names are accepted, so a guess can't withdraw the wrong player.
+    pub fn remove_player(&mut self, name: &str) -> Result<(), Error> {
+
+        let player = self.find_player_exactly(name)?;
+        self.players[player].active = false;
+
+        Ok(())
 
     }
 
@@ -60,12 +298,12 @@ impl Tournament {
             .collect()
     }
 
-    pub fn colour_difference_pairing(&self) -> impl Fn(PlayerID, PlayerID) -> Pairing + '_ {
+    pub fn get_colour_differences(&self) -> Vec<i32> {
     
         let mut colour_differences = vec![0; self.players.len()];
     
         for round in self.rounds.iter() {
-            for game in round.games.iter() {
+            for game in round.games.iter().filter(|game| !game.result.is_forfeit()) {
     
                 colour_differences[game.white_player] += 1;
                 colour_differences[game.black_player] -= 1;
@@ -73,16 +311,47 @@ impl Tournament {
             }
         }
     
-        move |player1, player2| Pairing::using_colour_differences(player1, player2, &colour_differences)
+        colour_differences
+    
+    }
+
+    /// +1 for each game as white, -1 for each game as black, in round order.
+    pub fn get_colour_histories(&self) -> Vec<Vec<i32>> {
+    
+        let mut colour_histories = vec![Vec::new(); self.players.len()];
+    
+        for round in self.rounds.iter() {
+            for game in round.games.iter().filter(|game| !game.result.is_forfeit()) {
+    
+                colour_histories[game.white_player].push(1);
+                colour_histories[game.black_player].push(-1);
+    
+            }
+        }
+    
+        colour_histories
     
     }
 
+    /// Gives players the colour they must have, otherwise the one their colour difference prefers.
+    pub fn colour_difference_pairing(&self) -> impl Fn(PlayerID, PlayerID) -> Pairing + '_ {
+
+        let colour_differences = self.get_colour_differences();
+        let required_colours = self.get_required_colours();
+
+        move |player1, player2| match (required_colours[player1], required_colours[player2]) {
+            (1, _) | (_, -1) => Pairing::new(player1, player2),
+            (-1, _) | (_, 1) => Pairing::new(player2, player1),
+            _ => Pairing::using_colour_differences(player1, player2, &colour_differences)
+        }
+    }
+
     pub fn get_already_played(&self) -> Vec<PlayerIDList> {
     
         let mut already_played = vec![PlayerIDList::new(); self.players.len()];
     
         for round in self.rounds.iter() {
-            for game in round.games.iter() {
+            for game in round.games.iter().filter(|game| !game.result.is_forfeit()) {
     
                 already_played[game.white_player].0.push(game.black_player);
                 already_played[game.black_player].0.push(game.white_player);
@@ -94,6 +363,60 @@ impl Tournament {
     
     }
 
+    /// Players with more than half the possible score when pairing the final round, whose colours may be ignored.
+    /// Nobody is a topscorer in other rounds or when the number of rounds isn't set.
+    pub fn get_topscorers(&self) -> Vec<bool> {
+
+        if !self.pairing_final_round() {
+            return vec![false; self.players.len()];
+        }
+
+        self.get_player_scores().into_iter()
+            .map(|score| score > self.rounds.len() as f32 / 2.0)
+            .collect()
+
+    }
+
+    /// The colour each player must get next, 1 for white, -1 for black and 0 if either will do:
+    /// the other colour after a colour difference of 2 or the same colour twice in a row.
+    /// Topscorers in the final round may get either colour.
+    pub fn get_required_colours(&self) -> Vec<i32> {
+
+        let topscorers = self.get_topscorers();
+
+        self.get_colour_histories().into_iter()
+            .zip(topscorers)
+            .map(|(history, topscorer)| {
+
+                let colour_difference: i32 = history.iter().sum();
+
+                match history[..] {
+                    _ if topscorer => 0,
+                    _ if colour_difference.abs() >= 2 => -colour_difference.signum(),
+                    [.., previous, last] if previous == last => -last,
+                    _ => 0
+                }
+            })
+            .collect()
+
+    }
+
+    /// Absolute pairing criteria: no rematches, and no two players who must both get the same colour
+    /// unless one of them is a topscorer in the final round.
+    pub fn pairing_allowed(&self) -> impl Fn(PlayerID, PlayerID) -> bool {
+
+        let already_played = self.get_already_played();
+        let required_colours = self.get_required_colours();
+
+        move |player1, player2| {
+
+            let same_colour_needed = required_colours[player1] != 0 && required_colours[player1] == required_colours[player2];
+
+            !same_colour_needed && !has_already_played(player1, player2, &already_played)
+
+        }
+    }
+
     pub fn calc_score(&self, player: PlayerID) -> f32 {
     
         self.rounds.iter()
@@ -125,7 +448,7 @@ impl Tournament {
         self.rounds.iter()
             .map(|round| {
     
-                for game in round.games.iter() {
+                for game in round.games.iter().filter(|game| !game.result.is_forfeit()) {
                     if game.white_player == player {
                         return game.result.score() * player_scores[game.black_player];
                     }
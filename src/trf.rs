@@ -0,0 +1,76 @@
+use crate::*;
+
+impl Tournament {
+
+    /// Writes the tournament as a FIDE Tournament Report File (TRF-16), using pairing numbers as starting ranks.
+    pub fn to_trf(&self) -> String {
+
+        let numbers = self.get_pairing_numbers();
+        let scores = self.get_player_scores();
+        let mut places = vec![0; self.players.len()];
+
+        for (idx, standing) in self.standings().iter().enumerate() {
+            places[standing.player] = idx + 1;
+        }
+
+        let mut trf = format!("062 {}\nXXR {}\n", self.players.len(), self.rounds.len());
+
+        let mut player_ids = self.get_all_player_ids().0;
+        player_ids.sort_by_key(|&id| numbers[id]);
+
+        for id in player_ids {
+
+            let player = &self.players[id];
+            let title = player.title.map_or("", Title::name);
+            let rating = player.rating.map(|rating| rating.to_string()).unwrap_or_default();
+            let name: String = player.name.chars().take(33).collect();
+
+            trf += &format!("001 {:>4}  {title:>3} {name:<33} {rating:>4} {:>3} {:>11} {:>10} {:>4.1} {:>4}", numbers[id], "", "", "", scores[id], places[id]);
+
+            for round in self.rounds.iter() {
+                trf += &trf_round_entry(id, round, &numbers);
+            }
+
+            trf += "\n";
+
+        }
+
+        trf
+
+    }
+}
+
+fn trf_round_entry(player: PlayerID, round: &Round, numbers: &[u32]) -> String {
+
+    if round.bye_player == Some(player) {
+        return "  0000 - U".to_string();
+    }
+
+    for game in round.games.iter() {
+
+        let (opponent, colour, result) = if game.white_player == player {
+            (game.black_player, 'w', game.result)
+        }
+        else if game.black_player == player {
+            (game.white_player, 'b', game.result.opposite())
+        }
+        else {
+            continue;
+        };
+
+        let result_char = match result {
+            GameResult::Win => '1',
+            GameResult::Draw => '=',
+            GameResult::Loss => '0',
+            GameResult::ForfeitWin => '+',
+            GameResult::ForfeitLoss | GameResult::DoubleForfeit => '-',
+            GameResult::Pending => ' '
+        };
+
+        return format!("  {:>4} {colour} {result_char}", numbers[opponent]);
+
+    }
+
+    "  0000 - Z".to_string()
+
+}
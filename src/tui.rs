@@ -0,0 +1,272 @@
+use swiss::*;
+
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{read, Event as TerminalEvent, KeyCode, KeyEventKind};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, size, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+
+use std::io::Write;
+
+const HELP: &str = "Up/Down: select board  1: white wins  =: draw  0: black wins  +/-: forfeit  u: unreport  p: pair next round  q: quit";
+
+struct Screen<'a> {
+    event: &'a mut Event,
+    section_idx: usize,
+    save_path: Option<&'a str>,
+    selected_board: usize,
+    status: String
+}
+
+/// Runs the full-screen interface for one section until the user quits, saving to `save_path` after every change.
+pub fn run(event: &mut Event, section_idx: usize, save_path: Option<&str>) -> Result<(), Error> {
+
+    let mut stdout = std::io::stdout();
+    let _terminal = TerminalGuard::enter(&mut stdout)?;
+
+    let mut screen = Screen {
+        event,
+        section_idx,
+        save_path,
+        selected_board: 0,
+        status: String::new()
+    };
+
+    screen.event_loop(&mut stdout)
+
+}
+
+// puts the terminal back however the screen is left, including by a panic
+struct TerminalGuard;
+
+impl TerminalGuard {
+
+    fn enter(stdout: &mut std::io::Stdout) -> Result<Self, Error> {
+
+        enable_raw_mode()?;
+
+        // created first so raw mode is left again if switching screens fails
+        let guard = Self;
+        execute!(stdout, EnterAlternateScreen, Hide)?;
+
+        Ok(guard)
+
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(std::io::stdout(), Show, LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+    }
+}
+
+impl Screen<'_> {
+
+    fn tournament(&self) -> &Tournament {
+        &self.event.sections[self.section_idx].tournament
+    }
+
+    fn event_loop(&mut self, stdout: &mut std::io::Stdout) -> Result<(), Error> {
+        loop {
+
+            self.draw(stdout)?;
+
+            let TerminalEvent::Key(key) = read()?
+            else {
+                continue;
+            };
+
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            let num_boards = self.tournament().current_round().map_or(0, |round| round.games.len());
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Up | KeyCode::Char('k') => self.selected_board = self.selected_board.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => self.selected_board = (self.selected_board + 1).min(num_boards.saturating_sub(1)),
+                KeyCode::Char('1') => self.report(GameResult::Win),
+                KeyCode::Char('=') => self.report(GameResult::Draw),
+                KeyCode::Char('0') => self.report(GameResult::Loss),
+                KeyCode::Char('+') => self.report(GameResult::ForfeitWin),
+                KeyCode::Char('-') => self.report(GameResult::ForfeitLoss),
+                KeyCode::Char('u') | KeyCode::Backspace | KeyCode::Delete => self.report(GameResult::Pending),
+                KeyCode::Char('p') => self.pair_next_round(),
+                _ => {}
+            }
+        }
+    }
+
+    fn report(&mut self, result: GameResult) {
+
+        let Some(round) = self.tournament().current_round()
+        else {
+            self.status = Error::TournamentNotStarted.to_string();
+            return;
+        };
+
+        let Some(game) = round.games.get(self.selected_board)
+        else {
+            return;
+        };
+
+        let board_number = game.board_number;
+        let num_boards = round.games.len();
+
+        self.status = match self.event.sections[self.section_idx].tournament.report_result(board_number, result) {
+            Ok(()) => format!("Board {board_number}: {}", result.as_notation()),
+            Err(err) => err.to_string()
+        };
+
+        if result != GameResult::Pending {
+            self.selected_board = (self.selected_board + 1).min(num_boards.saturating_sub(1));
+        }
+
+        self.save();
+
+    }
+
+    fn pair_next_round(&mut self) {
+
+        self.status = match self.event.pair_next_round(self.section_idx) {
+            Ok(used_system) if used_system != self.tournament().system => {
+                format!("Was not able to find {} pairings, settled with {} pairings", self.tournament().system.name(), used_system.name())
+            }
+            Ok(_) => format!("Paired round {}", self.tournament().rounds.len()),
+            Err(err) => err.to_string()
+        };
+
+        self.selected_board = 0;
+        self.save();
+
+    }
+
+    fn save(&mut self) {
+        if let Some(path) = self.save_path {
+            if let Err(err) = self.event.save(path) {
+                self.status = format!("Could not save: {err}");
+            }
+        }
+    }
+
+    fn draw(&self, stdout: &mut std::io::Stdout) -> Result<(), Error> {
+
+        let (columns, rows) = size()?;
+        let (columns, rows) = (columns as usize, rows as usize);
+        let pane_width = columns / 2;
+        let pane_height = rows.saturating_sub(4);
+
+        queue!(stdout, Clear(ClearType::All))?;
+
+        let tournament = self.tournament();
+        let scores = tournament.get_player_scores();
+
+        let section_prefix = if self.event.sections.len() > 1 {
+            format!("{}: ", tournament.name)
+        }
+        else {
+            String::new()
+        };
+
+        let title = match tournament.current_round() {
+            Some(round) => {
+
+                let pending_boards = round.pending_boards();
+
+                if pending_boards.is_empty() {
+                    format!("Round {}: all results in", tournament.rounds.len())
+                }
+                else {
+
+                    let boards: Vec<_> = pending_boards.iter()
+                        .map(|board_number| board_number.to_string())
+                        .collect();
+
+                    format!("Round {}: pending boards {}", tournament.rounds.len(), boards.join(", "))
+
+                }
+            }
+            None => "Tournament has not started, press p to pair the first round".to_string()
+        };
+
+        queue!(stdout, MoveTo(0, 0), Print(fit(&(section_prefix + &title), columns)))?;
+        queue!(stdout, MoveTo(0, 1), Print(fit("Board  White / Black", pane_width)))?;
+        queue!(stdout, MoveTo(pane_width as u16, 1), Print(fit(" ##  Score  SB Score  Name", columns - pane_width)))?;
+
+        if let Some(round) = tournament.current_round() {
+
+            let name_width = pane_width.saturating_sub(27) / 2;
+            let first_visible = self.selected_board.saturating_sub(pane_height.saturating_sub(1));
+
+            let mut lines: Vec<_> = round.games.iter()
+                .map(|game| {
+
+                    let result = if game.result == GameResult::Pending {
+                        ""
+                    }
+                    else {
+                        game.result.as_notation()
+                    };
+
+                    format!(
+                        "{:>5}  {} ({:.1}) {:^5} {} ({:.1})",
+                        game.board_number,
+                        fit(&tournament.players[game.white_player].label(), name_width),
+                        scores[game.white_player],
+                        result,
+                        fit(&tournament.players[game.black_player].label(), name_width),
+                        scores[game.black_player]
+                    )
+                })
+                .collect();
+
+            if let Some(bye_player) = round.bye_player {
+                lines.push(format!("  Bye  {}", tournament.players[bye_player].label()));
+            }
+
+            for (row, (idx, line)) in lines.iter().enumerate().skip(first_visible).take(pane_height).enumerate() {
+
+                queue!(stdout, MoveTo(0, row as u16 + 2))?;
+
+                if idx == self.selected_board {
+                    queue!(stdout, SetAttribute(Attribute::Reverse), Print(fit(line, pane_width.saturating_sub(1))), SetAttribute(Attribute::Reset))?;
+                }
+                else {
+                    queue!(stdout, Print(fit(line, pane_width.saturating_sub(1))))?;
+                }
+            }
+        }
+
+        for (row, standing) in tournament.standings().iter().take(pane_height).enumerate() {
+
+            let player = &tournament.players[standing.player];
+            let withdraw_star = if player.active { ' ' } else { '*' };
+
+            let line = format!(
+                "{:>3}  {:>5.1}{withdraw_star} {:>8.2}  {}",
+                standing.place,
+                standing.score,
+                standing.sonneborn_berger,
+                player.name
+            );
+
+            queue!(stdout, MoveTo(pane_width as u16, row as u16 + 2), Print(fit(&line, columns - pane_width)))?;
+
+        }
+
+        queue!(stdout, MoveTo(0, rows.saturating_sub(2) as u16), Print(fit(&self.status, columns)))?;
+        queue!(stdout, MoveTo(0, rows.saturating_sub(1) as u16), Print(fit(HELP, columns)))?;
+
+        stdout.flush()?;
+
+        Ok(())
+
+    }
+}
+
+// pads or truncates to exactly `width` characters
+fn fit(text: &str, width: usize) -> String {
+    format!("{:<width$.width$}", text)
+}